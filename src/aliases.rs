@@ -0,0 +1,246 @@
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::config::{Config, OrgPrefix};
+
+/// Renames the scopes returned by a backend before they are used, so
+/// package names can be shortened without renaming the packages.
+pub struct ScopeAliases {
+    org_prefix: OrgPrefix,
+    strip_orgs: Vec<String>,
+    renames: HashMap<String, String>,
+    rewrites: Vec<(Regex, String)>,
+}
+
+impl ScopeAliases {
+    pub fn new(config: &Config) -> ScopeAliases {
+        let rewrites = config
+            .aliases
+            .rewrite
+            .iter()
+            .map(|rule| {
+                let regex =
+                    Regex::new(&rule.pattern).expect("invalid regex found in aliases rewrite");
+                (regex, rule.replace.to_owned())
+            })
+            .collect();
+
+        ScopeAliases {
+            org_prefix: config.aliases.org_prefix,
+            strip_orgs: config
+                .aliases
+                .strip_orgs
+                .iter()
+                .map(|org| org.trim_start_matches('@').to_owned())
+                .collect(),
+            renames: config.aliases.rename.clone(),
+            rewrites,
+        }
+    }
+
+    /// Maps every scope to its alias after the org prefix handling, then an
+    /// exact rename or else every rewrite in order. Scopes aliased to nothing
+    /// are left out.
+    pub fn alias_all(&self, scopes: Vec<String>) -> HashMap<String, String> {
+        let scopes: BTreeSet<String> = scopes.into_iter().collect();
+        let mut aliased = HashMap::new();
+
+        for (scope, name) in self.handle_org_prefixes(scopes.into_iter().collect()) {
+            if let Some(alias) = self.alias(&scope, name) {
+                log::info!("aliased scope {} to {}\n", scope, alias);
+                aliased.insert(scope, alias);
+            }
+        }
+
+        aliased
+    }
+
+    fn alias(&self, scope: &str, name: String) -> Option<String> {
+        if let Some(renamed) = self.renames.get(scope) {
+            return Some(renamed.to_owned());
+        }
+
+        if let Some(renamed) = self.renames.get(&name) {
+            return Some(renamed.to_owned());
+        }
+
+        let rewritten = self.rewrites.iter().fold(name, |name, (regex, replace)| {
+            regex.replace_all(&name, replace.as_str()).into_owned()
+        });
+
+        if rewritten.is_empty() {
+            return None;
+        }
+
+        Some(rewritten)
+    }
+
+    /// Pairs every scope with its name after the org prefix handling. Scoped
+    /// packages whose stripped names collide are reported, their names are
+    /// left as is so a package always gets the same scope.
+    fn handle_org_prefixes(&self, scopes: Vec<String>) -> Vec<(String, String)> {
+        let names: Vec<(String, String)> = scopes
+            .into_iter()
+            .map(|scope| {
+                let name = self.handle_org_prefix(&scope);
+                (scope, name)
+            })
+            .collect();
+
+        let mut scopes_by_name: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+
+        for (scope, name) in names.iter() {
+            if !self.renames.contains_key(scope) {
+                scopes_by_name.entry(name).or_default().insert(scope);
+            }
+        }
+
+        for (name, scopes) in scopes_by_name {
+            if scopes.len() > 1 {
+                log::warn!(
+                    "scopes {} collide as {}, rename them or set aliases.org_prefix = \"join\"\n",
+                    scopes.into_iter().collect::<Vec<&str>>().join(", "),
+                    name
+                );
+            }
+        }
+
+        names
+    }
+
+    fn handle_org_prefix(&self, name: &str) -> String {
+        if !name.starts_with('@') {
+            return name.to_owned();
+        }
+
+        match self.org_prefix {
+            OrgPrefix::Keep => name.to_owned(),
+            OrgPrefix::Strip => strip_org_prefix(name),
+            OrgPrefix::Join => join_org_prefix(name),
+            OrgPrefix::StripListed => {
+                let org = name[1..].split('/').next().unwrap();
+
+                if self.strip_orgs.iter().any(|listed| listed == org) {
+                    strip_org_prefix(name)
+                } else {
+                    name.to_owned()
+                }
+            }
+        }
+    }
+}
+
+/// `@org/name` becomes `name`, a bare `@org` becomes `org`.
+fn strip_org_prefix(name: &str) -> String {
+    let name = name.trim_start_matches('@');
+
+    match name.split_once('/') {
+        Some((_, package)) if !package.is_empty() => package.to_owned(),
+        _ => name.trim_end_matches('/').to_owned(),
+    }
+}
+
+/// `@org/name` becomes `org-name`, a bare `@org` becomes `org`.
+fn join_org_prefix(name: &str) -> String {
+    name.trim_start_matches('@')
+        .trim_end_matches('/')
+        .replacen('/', "-", 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utilities::test_each;
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            scopes: Vec<&'a str>,
+            expected_scopes: Vec<&'a str>,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                description: "strips org prefix by default",
+                config: "",
+                scopes: vec!["@acme/ui", "api"],
+                expected_scopes: vec!["api", "ui"],
+            },
+            TestCase {
+                description: "uses the org name for a bare org",
+                config: "",
+                scopes: vec!["@acme"],
+                expected_scopes: vec!["acme"],
+            },
+            TestCase {
+                description: "joins org prefix when configured",
+                config: "[aliases]\norg_prefix = \"join\"",
+                scopes: vec!["@acme/ui", "@acme", "api"],
+                expected_scopes: vec!["acme", "acme-ui", "api"],
+            },
+            TestCase {
+                description: "strips only listed orgs when configured",
+                config: "[aliases]\norg_prefix = \"strip-listed\"\nstrip_orgs = [\"@acme\"]",
+                scopes: vec!["@acme/ui", "@vendor/ui-kit"],
+                expected_scopes: vec!["@vendor/ui-kit", "ui"],
+            },
+            TestCase {
+                description: "keeps the stripped names of scoped packages colliding once stripped",
+                config: "",
+                scopes: vec!["@web/utils", "@api/utils", "@web/ui"],
+                expected_scopes: vec!["ui", "utils"],
+            },
+            TestCase {
+                description: "keeps the stripped name of a scoped package colliding with an unscoped one",
+                config: "",
+                scopes: vec!["@web/utils", "utils"],
+                expected_scopes: vec!["utils"],
+            },
+            TestCase {
+                description: "does not report collisions for renamed packages",
+                config: "[aliases.rename]\n\"@api/utils\" = \"api-helpers\"",
+                scopes: vec!["@web/utils", "@api/utils"],
+                expected_scopes: vec!["api-helpers", "utils"],
+            },
+            TestCase {
+                description: "keeps org prefix when configured",
+                config: "[aliases]\norg_prefix = \"keep\"",
+                scopes: vec!["@acme/ui", "api"],
+                expected_scopes: vec!["@acme/ui", "api"],
+            },
+            TestCase {
+                description: "renames exact matches with or without org prefix",
+                config: "[aliases.rename]\n\"web-frontend-dashboard-app\" = \"dashboard\"\n\"@acme/ui\" = \"design-system\"",
+                scopes: vec!["web-frontend-dashboard-app", "@acme/ui"],
+                expected_scopes: vec!["dashboard", "design-system"],
+            },
+            TestCase {
+                description: "applies rewrites in order to scopes that are not renamed",
+                config: "[aliases.rename]\nweb-app = \"web\"\n\n[[aliases.rewrite]]\npattern = \"-(app|lib)$\"\n\n[[aliases.rewrite]]\npattern = \"^shared-(.*)\"\nreplace = \"$1\"",
+                scopes: vec!["web-app", "billing-app", "shared-utils-lib"],
+                expected_scopes: vec!["billing", "utils", "web"],
+            },
+            TestCase {
+                description: "de-duplicates scopes that end up with the same alias",
+                config: "[[aliases.rewrite]]\npattern = \"-(app|lib)$\"",
+                scopes: vec!["ui-app", "ui-lib", "ui"],
+                expected_scopes: vec!["ui"],
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let config: Config = toml::from_str(tc.config).unwrap();
+            let aliases = ScopeAliases::new(&config);
+            let scopes = tc.scopes.iter().map(|s| s.to_string()).collect();
+
+            let aliased: BTreeSet<String> = aliases.alias_all(scopes).into_values().collect();
+
+            assert_eq!(aliased.into_iter().collect::<Vec<_>>(), tc.expected_scopes);
+        });
+    }
+}
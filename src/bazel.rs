@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use crate::config::Config;
+use crate::Monorepo;
+
+static BUILD_FILES: [&str; 3] = ["BUILD.bazel", "BUILD", "BUCK"];
+
+static WORKSPACE_FILES: [&str; 4] = [
+    "MODULE.bazel",
+    "WORKSPACE.bazel",
+    "WORKSPACE",
+    ".buckconfig",
+];
+
+pub struct BazelMonorepo {
+    repo_root: PathBuf,
+    depth: Option<usize>,
+}
+
+impl Monorepo for BazelMonorepo {
+    fn new(repo_root: PathBuf, config: &Config) -> Option<Box<dyn Monorepo>> {
+        if !BazelMonorepo::is_workspace_root(&repo_root) {
+            return None;
+        }
+
+        Some(Box::new(BazelMonorepo {
+            repo_root,
+            depth: config.bazel.depth,
+        }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        let package_path = self.get_package_path_for_file(path)?;
+
+        let segments: Vec<&str> = package_path.split('/').collect();
+        let depth = self.depth.unwrap_or(segments.len()).max(1);
+
+        segments
+            .get(depth.min(segments.len()) - 1)
+            .map(|segment| segment.to_string())
+    }
+}
+
+impl BazelMonorepo {
+    /// Finds the package owning a staged file, i.e. the nearest directory
+    /// with a BUILD file, without crossing into the enclosing workspace root.
+    fn get_package_path_for_file(&self, entry: &str) -> Option<String> {
+        let abs_path = self.repo_root.join(entry);
+
+        for dir in abs_path.ancestors() {
+            if dir.eq(self.repo_root.as_path()) || BazelMonorepo::is_workspace_root(dir) {
+                return None;
+            }
+
+            if BUILD_FILES
+                .iter()
+                .any(|build_file| dir.join(build_file).is_file())
+            {
+                let dir_relative = dir.strip_prefix(&self.repo_root).unwrap();
+                let segments: Vec<&str> = dir_relative
+                    .iter()
+                    .filter_map(|segment| segment.to_str())
+                    .collect();
+
+                return Some(segments.join("/"));
+            }
+        }
+
+        None
+    }
+
+    fn is_workspace_root(dir: &Path) -> bool {
+        WORKSPACE_FILES
+            .iter()
+            .any(|workspace_file| dir.join(workspace_file).is_file())
+    }
+}
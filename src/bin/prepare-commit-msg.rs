@@ -3,6 +3,8 @@ use nvim_conventional_commits::{
 };
 use std::{env, path::Path};
 
+static PRINT_BACKEND_FLAG: &str = "--print-backend";
+
 fn main() {
     let cwd = env::current_dir().unwrap();
     let args: Vec<String> = env::args().collect();
@@ -10,10 +12,18 @@ fn main() {
 
     Logger::new().init().expect("failed to initialize logger");
 
+    let scope_finder = DefaultScopeFinder::new(&cwd);
+
+    if commit_msg_file == PRINT_BACKEND_FLAG {
+        println!("{}", scope_finder.backend_name().unwrap_or("none"));
+        return;
+    }
+
     let prepare_commit_msg = PrepareCommitMessage {
         commit_msg_file: Path::new(commit_msg_file),
         commit_source: args.get(2).map(|cs| cs.as_str()),
-        commit_msg_generator: &DefaultScopeFinder::new(&cwd),
+        commit_msg_generator: &scope_finder,
+        config: scope_finder.config(),
     };
 
     prepare_commit_msg.update_commit().unwrap();
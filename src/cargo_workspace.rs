@@ -0,0 +1,97 @@
+use serde::Deserialize;
+use std::{fs, path::Path, path::PathBuf};
+
+use crate::config::Config;
+use crate::manifest::{build_packages_globs, find_nearest_manifest, PackageGlobs};
+use crate::Monorepo;
+
+#[derive(Deserialize)]
+struct CargoManifest {
+    workspace: Option<CargoWorkspace>,
+    package: Option<CargoPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CargoPackage {
+    name: String,
+}
+
+pub struct CargoMonorepo {
+    repo_root: PathBuf,
+    members_globs: PackageGlobs,
+}
+
+impl Monorepo for CargoMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let workspace =
+            CargoMonorepo::parse_cargo_manifest(&repo_root.join("Cargo.toml"))?.workspace?;
+
+        let patterns: Vec<String> = workspace
+            .members
+            .into_iter()
+            .chain(
+                workspace
+                    .exclude
+                    .into_iter()
+                    .map(|path| format!("!{}", path)),
+            )
+            .collect();
+
+        let members_globs = build_packages_globs(&patterns)
+            .expect("invalid glob found in Cargo.toml workspace members");
+
+        Some(Box::new(CargoMonorepo {
+            repo_root,
+            members_globs,
+        }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        find_nearest_manifest(
+            &self.repo_root,
+            path,
+            "Cargo.toml",
+            |dir_relative, manifest_path| {
+                if !self.members_globs.is_match(dir_relative) {
+                    return None;
+                }
+
+                CargoMonorepo::parse_cargo_manifest(manifest_path)?
+                    .package
+                    .map(|package| package.name)
+            },
+        )
+    }
+
+    fn get_group_for_file(&self, path: &str) -> Option<String> {
+        find_nearest_manifest(&self.repo_root, path, "Cargo.toml", |dir_relative, _| {
+            if !self.members_globs.is_match(dir_relative) {
+                return None;
+            }
+
+            self.members_globs.group_for_dir(dir_relative)
+        })
+    }
+}
+
+impl CargoMonorepo {
+    fn parse_cargo_manifest(manifest_path: &Path) -> Option<CargoManifest> {
+        let contents = fs::read_to_string(manifest_path).ok()?;
+
+        match toml::from_str(&contents) {
+            Ok(manifest) => Some(manifest),
+            Err(e) => {
+                log::info!("failed to parse {:#?} {}\n", manifest_path, e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use crate::config::Config;
+use crate::Monorepo;
+
+/// Locations GitHub looks for a CODEOWNERS file in, in order.
+static CODEOWNERS_FILES: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+struct CodeownersRule {
+    globset: GlobSet,
+    owners: Vec<String>,
+}
+
+/// Scopes staged files by the owners of the last CODEOWNERS rule matching
+/// them, mirroring how GitHub picks the owners for review requests.
+pub struct CodeownersMonorepo {
+    rules: Vec<CodeownersRule>,
+    owner_scopes: HashMap<String, String>,
+}
+
+impl Monorepo for CodeownersMonorepo {
+    fn new(repo_root: PathBuf, config: &Config) -> Option<Box<dyn Monorepo>> {
+        let codeowners = CODEOWNERS_FILES
+            .iter()
+            .find_map(|name| fs::read_to_string(repo_root.join(name)).ok())?;
+
+        let rules = codeowners
+            .lines()
+            .filter_map(CodeownersMonorepo::parse_rule)
+            .collect();
+
+        Some(Box::new(CodeownersMonorepo {
+            rules,
+            owner_scopes: config.codeowners.owners.clone(),
+        }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        let rule = self
+            .rules
+            .iter()
+            .rev()
+            .find(|rule| rule.globset.is_match(path))?;
+
+        rule.owners
+            .iter()
+            .find_map(|owner| self.owner_scopes.get(owner).cloned())
+            .or_else(|| rule.owners.first().map(|owner| owner_scope(owner)))
+    }
+}
+
+impl CodeownersMonorepo {
+    fn parse_rule(line: &str) -> Option<CodeownersRule> {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let mut parts = line
+            .split('#')
+            .next()
+            .unwrap()
+            .split_whitespace()
+            .map(|part| part.to_owned());
+        let pattern = parts.next()?;
+        let owners = parts.collect();
+
+        let mut globset = GlobSetBuilder::new();
+
+        for glob in CodeownersMonorepo::pattern_globs(&pattern) {
+            match GlobBuilder::new(&glob).literal_separator(true).build() {
+                Ok(glob) => {
+                    globset.add(glob);
+                }
+                Err(e) => {
+                    log::info!("invalid CODEOWNERS pattern {} {}\n", pattern, e);
+                    return None;
+                }
+            }
+        }
+
+        Some(CodeownersRule {
+            globset: globset.build().ok()?,
+            owners,
+        })
+    }
+
+    /// Translates a CODEOWNERS pattern into globs over repo relative paths.
+    /// Patterns are anchored to the repo root when they start with or contain
+    /// a `/`, and match everything inside a directory unless they end in `/*`.
+    fn pattern_globs(pattern: &str) -> Vec<String> {
+        let anchored = pattern.trim_end_matches('/').contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        let directory_only = pattern.ends_with('/');
+        let pattern = pattern.trim_end_matches('/');
+
+        let glob = if anchored {
+            pattern.to_owned()
+        } else {
+            format!("**/{}", pattern)
+        };
+
+        if pattern.ends_with("/*") {
+            vec![glob]
+        } else if directory_only {
+            vec![format!("{}/**", glob)]
+        } else {
+            vec![format!("{}/**", glob), glob]
+        }
+    }
+}
+
+/// Derives a scope from an owner handle, `@org/payments` and `@payments`
+/// become `payments`, emails use the part before the `@`.
+fn owner_scope(owner: &str) -> String {
+    match owner.strip_prefix('@') {
+        Some(handle) => handle.rsplit('/').next().unwrap().to_owned(),
+        None => owner.split('@').next().unwrap().to_owned(),
+    }
+}
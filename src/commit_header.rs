@@ -0,0 +1,143 @@
+use regex::Regex;
+
+/// The first line of a conventional commit, `type(scope)!: description`.
+pub struct CommitHeader {
+    pub commit_type: String,
+    /// Comma separated scopes, empty when the header has none.
+    pub scopes: Vec<String>,
+    pub breaking: bool,
+    /// Everything after the `:`, kept as typed including the leading space.
+    pub description: String,
+}
+
+impl CommitHeader {
+    pub fn parse(header: &str) -> Option<CommitHeader> {
+        let header_regex =
+            Regex::new(r"^(?P<type>[A-Za-z][\w-]*)(?:\((?P<scope>[^()\r\n]*)\))?(?P<breaking>!)?:(?P<description>.*)$")
+                .unwrap();
+        let captures = header_regex.captures(header)?;
+
+        let scopes = match captures.name("scope") {
+            Some(scope) => scope
+                .as_str()
+                .split(',')
+                .map(|scope| scope.trim())
+                .filter(|scope| !scope.is_empty())
+                .map(|scope| scope.to_owned())
+                .collect(),
+            None => vec![],
+        };
+
+        Some(CommitHeader {
+            commit_type: captures["type"].to_owned(),
+            scopes,
+            breaking: captures.name("breaking").is_some(),
+            description: captures["description"].to_owned(),
+        })
+    }
+
+    pub fn render(&self) -> String {
+        let scopes = if self.scopes.is_empty() {
+            String::new()
+        } else {
+            format!("({})", self.scopes.join(","))
+        };
+
+        format!(
+            "{}{}{}:{}",
+            self.commit_type,
+            scopes,
+            if self.breaking { "!" } else { "" },
+            self.description
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utilities::test_each;
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            header: &'a str,
+            expected: Option<(&'a str, Vec<&'a str>, bool, &'a str)>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                description: "parses a type and description",
+                header: "docs: update readme",
+                expected: Some(("docs", vec![], false, " update readme")),
+            },
+            TestCase {
+                description: "parses a breaking change",
+                header: "feat!: drop node 12",
+                expected: Some(("feat", vec![], true, " drop node 12")),
+            },
+            TestCase {
+                description: "parses scopes",
+                header: "fix(api, auth): handle expired tokens",
+                expected: Some(("fix", vec!["api", "auth"], false, " handle expired tokens")),
+            },
+            TestCase {
+                description: "parses scopes of a breaking change",
+                header: "feat(ui)!: new theme",
+                expected: Some(("feat", vec!["ui"], true, " new theme")),
+            },
+            TestCase {
+                description: "parses an empty description",
+                header: "chore:",
+                expected: Some(("chore", vec![], false, "")),
+            },
+            TestCase {
+                description: "does not parse a message without a type",
+                header: "update readme",
+                expected: None,
+            },
+            TestCase {
+                description: "does not parse an unclosed scope",
+                header: "fix(api: something",
+                expected: None,
+            },
+            TestCase {
+                description: "does not parse a type after other words",
+                header: "see docs: here",
+                expected: None,
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let header = CommitHeader::parse(tc.header);
+
+            match tc.expected {
+                Some((commit_type, scopes, breaking, description)) => {
+                    let header = header.unwrap();
+
+                    assert_eq!(header.commit_type, commit_type);
+                    assert_eq!(header.scopes, scopes);
+                    assert_eq!(header.breaking, breaking);
+                    assert_eq!(header.description, description);
+                }
+                None => assert!(header.is_none()),
+            }
+        })
+    }
+
+    #[test]
+    fn it_renders_the_header() {
+        let header = CommitHeader {
+            commit_type: "feat".to_owned(),
+            scopes: vec!["api".to_owned(), "ui".to_owned()],
+            breaking: true,
+            description: " new endpoint".to_owned(),
+        };
+
+        assert_eq!(header.render(), "feat(api,ui)!: new endpoint");
+    }
+}
@@ -0,0 +1,274 @@
+use regex::Regex;
+use std::path::Path;
+
+use crate::config::{Config, TypeRule};
+use crate::path_patterns::PathPatterns;
+use crate::staged::StagedChange;
+
+/// Rules used when `type_rules` is not configured, as (type, paths, new files
+/// only, added line regex).
+static DEFAULT_TYPE_RULES: [(&str, &[&str], bool, Option<&str>); 5] = [
+    ("docs", &["*.md", "*.mdx", "*.rst", "docs/"], false, None),
+    (
+        "test",
+        &[
+            "test/",
+            "tests/",
+            "__tests__/",
+            "__snapshots__/",
+            "*.test.*",
+            "*.spec.*",
+            "*_test.go",
+            "*_test.py",
+            "test_*.py",
+        ],
+        false,
+        None,
+    ),
+    (
+        "ci",
+        &[".github/workflows/", ".gitlab-ci.yml", ".circleci/"],
+        false,
+        None,
+    ),
+    (
+        "build",
+        &[
+            "package.json",
+            "package-lock.json",
+            "yarn.lock",
+            "pnpm-lock.yaml",
+            "pnpm-workspace.yaml",
+            "lerna.json",
+            "nx.json",
+            "tsconfig*.json",
+            "*.config.js",
+            "*.config.mjs",
+            "*.config.ts",
+            "Dockerfile",
+            "Makefile",
+            "Cargo.toml",
+            "Cargo.lock",
+            "go.mod",
+            "go.sum",
+            "pom.xml",
+            "build.gradle",
+            "build.gradle.kts",
+            "BUILD",
+            "BUILD.bazel",
+        ],
+        false,
+        None,
+    ),
+    (
+        "feat",
+        &["*"],
+        true,
+        Some(r"^\s*(export|pub|module\.exports)\b"),
+    ),
+];
+
+struct TypeRuleMatcher {
+    commit_type: String,
+    paths: PathPatterns,
+    new_files: bool,
+    added_line: Option<Regex>,
+}
+
+/// Infers the commit type from the staged changes. Rules are tried in order
+/// and the first one matching wins.
+pub struct CommitTypeRules {
+    rules: Vec<TypeRuleMatcher>,
+}
+
+impl CommitTypeRules {
+    pub fn new(repo_root: &Path, config: &Config) -> CommitTypeRules {
+        let rules = match &config.type_rules {
+            Some(type_rules) => type_rules
+                .iter()
+                .map(|rule| CommitTypeRules::build_rule(repo_root, rule))
+                .collect(),
+            None => DEFAULT_TYPE_RULES
+                .iter()
+                .map(|(commit_type, paths, new_files, added_line)| {
+                    let rule = TypeRule {
+                        commit_type: commit_type.to_string(),
+                        paths: paths.iter().map(|path| path.to_string()).collect(),
+                        new_files: *new_files,
+                        added_line: added_line.map(|added_line| added_line.to_owned()),
+                    };
+
+                    CommitTypeRules::build_rule(repo_root, &rule)
+                })
+                .collect(),
+        };
+
+        CommitTypeRules { rules }
+    }
+
+    fn build_rule(repo_root: &Path, rule: &TypeRule) -> TypeRuleMatcher {
+        TypeRuleMatcher {
+            commit_type: rule.commit_type.to_owned(),
+            paths: PathPatterns::new(repo_root, &rule.paths)
+                .expect("invalid pattern found in type_rules paths"),
+            new_files: rule.new_files,
+            added_line: rule.added_line.as_ref().map(|added_line| {
+                Regex::new(added_line).expect("invalid regex found in type_rules added_line")
+            }),
+        }
+    }
+
+    pub fn infer(&self, staged_changes: &[StagedChange]) -> Option<String> {
+        if staged_changes.is_empty() {
+            return None;
+        }
+
+        self.rules
+            .iter()
+            .find(|rule| {
+                let matched: Vec<&StagedChange> = if rule.new_files {
+                    staged_changes
+                        .iter()
+                        .filter(|change| change.is_new && rule.paths.is_match(&change.path))
+                        .collect()
+                } else if staged_changes
+                    .iter()
+                    .all(|change| rule.paths.is_match(&change.path))
+                {
+                    staged_changes.iter().collect()
+                } else {
+                    vec![]
+                };
+
+                match &rule.added_line {
+                    Some(added_line) => matched.iter().any(|change| {
+                        change
+                            .added_lines
+                            .iter()
+                            .any(|line| added_line.is_match(line))
+                    }),
+                    None => !matched.is_empty(),
+                }
+            })
+            .map(|rule| {
+                log::info!("inferred commit type {}\n", rule.commit_type);
+                rule.commit_type.to_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utilities::test_each;
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            staged_changes: Vec<(&'a str, bool, Vec<&'a str>)>,
+            expected_type: Option<&'a str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                description: "infers docs for markdown files and docs/",
+                config: "",
+                staged_changes: vec![
+                    ("README.md", false, vec![]),
+                    ("docs/setup.html", false, vec![]),
+                ],
+                expected_type: Some("docs"),
+            },
+            TestCase {
+                description: "infers test for test files",
+                config: "",
+                staged_changes: vec![
+                    ("packages/app/src/app.test.ts", false, vec![]),
+                    ("packages/app/tests/fixture.json", true, vec![]),
+                ],
+                expected_type: Some("test"),
+            },
+            TestCase {
+                description: "infers ci for workflows",
+                config: "",
+                staged_changes: vec![(".github/workflows/ci.yml", false, vec![])],
+                expected_type: Some("ci"),
+            },
+            TestCase {
+                description: "infers build for build config",
+                config: "",
+                staged_changes: vec![
+                    ("packages/app/package.json", false, vec![]),
+                    ("tsconfig.base.json", false, vec![]),
+                ],
+                expected_type: Some("build"),
+            },
+            TestCase {
+                description: "infers feat for new files with exports",
+                config: "",
+                staged_changes: vec![
+                    (
+                        "packages/app/src/button.ts",
+                        true,
+                        vec!["export const Button = 1;"],
+                    ),
+                    ("packages/app/README.md", false, vec![]),
+                ],
+                expected_type: Some("feat"),
+            },
+            TestCase {
+                description: "infers nothing for new files without exports",
+                config: "",
+                staged_changes: vec![("packages/app/src/button.ts", true, vec!["const a = 1;"])],
+                expected_type: None,
+            },
+            TestCase {
+                description: "infers nothing for mixed changes",
+                config: "",
+                staged_changes: vec![
+                    ("packages/app/src/app.ts", false, vec![]),
+                    ("README.md", false, vec![]),
+                ],
+                expected_type: None,
+            },
+            TestCase {
+                description: "uses the configured rules instead of the defaults",
+                config: "[[type_rules]]\ntype = \"deps\"\npaths = [\"package.json\", \"*.lock\"]",
+                staged_changes: vec![("package.json", false, vec![])],
+                expected_type: Some("deps"),
+            },
+            TestCase {
+                description: "infers nothing with no configured rules",
+                config: "type_rules = []",
+                staged_changes: vec![("README.md", false, vec![])],
+                expected_type: None,
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let config: Config = toml::from_str(tc.config).unwrap();
+            let rules = CommitTypeRules::new(Path::new("/repo"), &config);
+
+            let staged_changes: Vec<StagedChange> = tc
+                .staged_changes
+                .into_iter()
+                .map(|(path, is_new, added_lines)| StagedChange {
+                    path: path.to_owned(),
+                    is_new,
+                    lines_changed: added_lines.len(),
+                    added_lines: added_lines.iter().map(|line| line.to_string()).collect(),
+                })
+                .collect();
+
+            assert_eq!(
+                rules.infer(&staged_changes),
+                tc.expected_type.map(|t| t.to_owned())
+            );
+        })
+    }
+}
@@ -0,0 +1,356 @@
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use crate::config::CommitTypes;
+
+/// Config files in the order commitlint looks them up after package.json.
+/// YAML being a superset of JSON, every one of them is parsed as YAML.
+static COMMITLINT_FILES: [&str; 4] = [
+    ".commitlintrc",
+    ".commitlintrc.json",
+    ".commitlintrc.yaml",
+    ".commitlintrc.yml",
+];
+
+static UNSUPPORTED_COMMITLINT_FILES: [&str; 6] = [
+    ".commitlintrc.js",
+    ".commitlintrc.cjs",
+    ".commitlintrc.ts",
+    "commitlint.config.js",
+    "commitlint.config.cjs",
+    "commitlint.config.ts",
+];
+
+static PACKAGE_JSON_COMMITLINT_KEY: &str = "commitlint";
+
+#[derive(Deserialize)]
+struct CommitlintConfig {
+    #[serde(default)]
+    rules: HashMap<String, Value>,
+}
+
+/// A commitlint rule given as `[level, when, value]`.
+struct Rule {
+    level: u64,
+    when: String,
+    value: Value,
+}
+
+/// The commitlint rules the hook applies to the messages it generates.
+pub struct Commitlint {
+    rules: HashMap<String, Rule>,
+}
+
+impl Commitlint {
+    pub fn load(repo_root: &Path) -> Option<Commitlint> {
+        for name in UNSUPPORTED_COMMITLINT_FILES.iter() {
+            if repo_root.join(name).exists() {
+                log::info!(
+                    "ignoring {}, only JSON and YAML commitlint configs are read\n",
+                    name
+                );
+            }
+        }
+
+        // commitlint reads the package.json key before any config file
+        Commitlint::load_package_json(repo_root).or_else(|| {
+            COMMITLINT_FILES.iter().find_map(|name| {
+                let contents = fs::read_to_string(repo_root.join(name)).ok()?;
+                Commitlint::parse(name, &contents)
+            })
+        })
+    }
+
+    fn load_package_json(repo_root: &Path) -> Option<Commitlint> {
+        let package_json_file = fs::File::open(repo_root.join("package.json")).ok()?;
+        let mut package_json: Value = serde_json::from_reader(package_json_file).ok()?;
+        let config = package_json.get_mut(PACKAGE_JSON_COMMITLINT_KEY)?.take();
+
+        match serde_json::from_value(config) {
+            Ok(config) => Some(Commitlint::from_config(config)),
+            Err(e) => {
+                log::info!(
+                    "failed to parse package.json {} {}\n",
+                    PACKAGE_JSON_COMMITLINT_KEY,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn parse(name: &str, contents: &str) -> Option<Commitlint> {
+        match serde_yaml::from_str(contents) {
+            Ok(config) => Some(Commitlint::from_config(config)),
+            Err(e) => {
+                log::info!("failed to parse {} {}\n", name, e);
+                None
+            }
+        }
+    }
+
+    fn from_config(config: CommitlintConfig) -> Commitlint {
+        let rules = config
+            .rules
+            .into_iter()
+            .filter_map(|(name, rule)| {
+                let rule = rule.as_array()?;
+
+                let rule = Rule {
+                    level: rule.first()?.as_u64()?,
+                    when: rule
+                        .get(1)
+                        .and_then(|when| when.as_str())
+                        .unwrap_or("always")
+                        .to_owned(),
+                    value: rule.get(2).cloned().unwrap_or(Value::Null),
+                };
+
+                Some((name, rule))
+            })
+            .collect();
+
+        Commitlint { rules }
+    }
+
+    /// Rule by name, unless it is disabled with level `0`.
+    fn rule(&self, name: &str) -> Option<&Rule> {
+        self.rules.get(name).filter(|rule| rule.level > 0)
+    }
+
+    pub fn type_enum(&self) -> Option<CommitTypes> {
+        let rule = self.rule("type-enum")?;
+
+        Some(CommitTypes::TypeEnum(
+            rule.level as u8,
+            rule.when.to_owned(),
+            string_values(&rule.value),
+        ))
+    }
+
+    pub fn header_max_length(&self) -> Option<usize> {
+        let rule = self.rule("header-max-length")?;
+
+        if rule.when != "always" {
+            return None;
+        }
+
+        rule.value.as_u64().map(|max_length| max_length as usize)
+    }
+
+    /// Converts the scopes to the first `scope-case`, then leaves out those
+    /// not allowed by `scope-enum`.
+    pub fn apply_scope_rules(&self, scopes: Vec<String>) -> Vec<String> {
+        let case = self
+            .rule("scope-case")
+            .filter(|rule| rule.when == "always")
+            .and_then(|rule| string_values(&rule.value).into_iter().next());
+
+        let scopes = scopes.into_iter().map(|scope| match &case {
+            Some(case) => to_case(&scope, case),
+            None => scope,
+        });
+
+        let scope_enum = self
+            .rule("scope-enum")
+            .map(|rule| (rule.when == "always", string_values(&rule.value)))
+            .filter(|(_, allowed)| !allowed.is_empty());
+
+        let mut scopes: Vec<String> = scopes
+            .filter(|scope| match &scope_enum {
+                Some((always, allowed)) => {
+                    let is_allowed = allowed.contains(scope) == *always;
+
+                    if !is_allowed {
+                        log::info!("dropping scope {} not allowed by scope-enum\n", scope);
+                    }
+
+                    is_allowed
+                }
+                None => true,
+            })
+            .collect();
+
+        // scopes ordered by weight can end up with the same name anywhere
+        let mut seen = HashSet::new();
+        scopes.retain(|scope| seen.insert(scope.clone()));
+        scopes
+    }
+}
+
+/// A rule value given as a single string or a list of them.
+fn string_values(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(value) => vec![value.to_owned()],
+        Value::Array(values) => values
+            .iter()
+            .filter_map(|value| value.as_str())
+            .map(|value| value.to_owned())
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Converts every `/` separated segment of the scope to a commitlint case.
+fn to_case(scope: &str, case: &str) -> String {
+    scope
+        .split('/')
+        .map(|segment| {
+            let words = split_words(segment);
+
+            match case {
+                "lower-case" => segment.to_lowercase(),
+                "upper-case" => segment.to_uppercase(),
+                "kebab-case" => words.join("-"),
+                "snake-case" => words.join("_"),
+                "camel-case" => {
+                    let mut words = words.iter();
+                    let first = words.next().cloned().unwrap_or_default();
+                    first + &words.map(|word| capitalize(word)).collect::<String>()
+                }
+                "pascal-case" => words.iter().map(|word| capitalize(word)).collect(),
+                _ => {
+                    log::info!("unsupported scope-case {}\n", case);
+                    segment.to_owned()
+                }
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+/// Lowercase words of a name, split on separators and case changes.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut prev_is_lower = false;
+
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            prev_is_lower = false;
+            continue;
+        }
+
+        if c.is_uppercase() && prev_is_lower && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+
+        prev_is_lower = c.is_lowercase() || c.is_numeric();
+        word.extend(c.to_lowercase());
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utilities::test_each;
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            scopes: Vec<&'a str>,
+            expected_scopes: Vec<&'a str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                description: "keeps scopes without rules",
+                config: "{}",
+                scopes: vec!["web-app", "utils"],
+                expected_scopes: vec!["web-app", "utils"],
+            },
+            TestCase {
+                description: "keeps only scopes allowed by scope-enum",
+                config: r#"{ "rules": { "scope-enum": [2, "always", ["web-app", "docs"]] } }"#,
+                scopes: vec!["web-app", "utils"],
+                expected_scopes: vec!["web-app"],
+            },
+            TestCase {
+                description: "leaves out scopes disallowed by a never scope-enum",
+                config: "rules:\n  scope-enum: [2, never, [utils]]",
+                scopes: vec!["web-app", "utils"],
+                expected_scopes: vec!["web-app"],
+            },
+            TestCase {
+                description: "ignores disabled rules",
+                config: "rules:\n  scope-enum: [0, always, [docs]]",
+                scopes: vec!["web-app"],
+                expected_scopes: vec!["web-app"],
+            },
+            TestCase {
+                description: "converts scopes to the scope-case",
+                config: "rules:\n  scope-case: [2, always, kebab-case]",
+                scopes: vec!["webApp", "shared_utils", "apps/AdminPanel"],
+                expected_scopes: vec!["web-app", "shared-utils", "apps/admin-panel"],
+            },
+            TestCase {
+                description: "converts scopes to the first scope-case listed",
+                config: "rules:\n  scope-case: [2, always, [camel-case, kebab-case]]",
+                scopes: vec!["web-app", "Shared_utils"],
+                expected_scopes: vec!["webApp", "sharedUtils"],
+            },
+            TestCase {
+                description: "removes scopes converted to the same name while keeping their order",
+                config: "rules:\n  scope-case: [2, always, kebab-case]",
+                scopes: vec!["fooBar", "baz", "foo_bar"],
+                expected_scopes: vec!["foo-bar", "baz"],
+            },
+            TestCase {
+                description: "applies scope-enum to converted scopes",
+                config: "rules:\n  scope-case: [2, always, pascal-case]\n  scope-enum: [2, always, [WebApp]]",
+                scopes: vec!["web-app", "utils"],
+                expected_scopes: vec!["WebApp"],
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let commitlint = Commitlint::parse(tc.description, tc.config).unwrap();
+            let scopes = tc.scopes.iter().map(|s| s.to_string()).collect();
+
+            assert_eq!(commitlint.apply_scope_rules(scopes), tc.expected_scopes);
+        })
+    }
+
+    #[test]
+    fn it_reads_the_type_enum_and_header_max_length_rules() {
+        let commitlint = Commitlint::parse(
+            ".commitlintrc.yaml",
+            "rules:\n  type-enum: [2, always, [feat, deps]]\n  header-max-length: [2, always, 72]",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            commitlint.type_enum(),
+            Some(CommitTypes::TypeEnum(2, when, types)) if when == "always" && types == vec!["feat", "deps"]
+        ));
+        assert_eq!(commitlint.header_max_length(), Some(72));
+    }
+}
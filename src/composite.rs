@@ -0,0 +1,72 @@
+use std::cmp::Reverse;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::Config;
+use crate::{detect_monorepo, Monorepo};
+
+pub static COMPOSITE_BACKEND: &str = "composite";
+
+/// Combines the backends configured in `monorepos`, each rooted at its own
+/// subdirectory. Staged files are handed to the backend of the most nested
+/// root containing them, relative to that root.
+pub struct CompositeMonorepo {
+    members: Vec<(PathBuf, Box<dyn Monorepo>)>,
+}
+
+impl Monorepo for CompositeMonorepo {
+    fn new(repo_root: PathBuf, config: &Config) -> Option<Box<dyn Monorepo>> {
+        if config.monorepos.is_empty() {
+            return None;
+        }
+
+        let mut members = vec![];
+
+        for member in config.monorepos.iter() {
+            let member_root: PathBuf = Path::new(&member.path)
+                .components()
+                .filter(|component| !matches!(component, Component::CurDir))
+                .collect();
+
+            match detect_monorepo(
+                &repo_root.join(&member_root),
+                member.backend.as_deref(),
+                config,
+            ) {
+                Some((backend, monorepo)) => {
+                    log::info!("using {} backend for {}\n", backend, member.path);
+                    members.push((member_root, monorepo));
+                }
+                None => log::info!("no monorepo backend found for {}\n", member.path),
+            }
+        }
+
+        members.sort_by_key(|(root, _)| Reverse(root.components().count()));
+
+        Some(Box::new(CompositeMonorepo { members }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        let path = Path::new(path);
+
+        self.members.iter().find_map(|(root, monorepo)| {
+            let member_path = path.strip_prefix(root).ok()?;
+            monorepo.get_scope_for_file(member_path.to_str()?)
+        })
+    }
+
+    fn get_group_for_file(&self, path: &str) -> Option<String> {
+        let path = Path::new(path);
+
+        // the group comes from the member that scoped the file, relative to
+        // the repo root, e.g. `web/apps`
+        let (root, monorepo) = self.members.iter().find(|(root, monorepo)| {
+            let member_path = path.strip_prefix(root).ok().and_then(|path| path.to_str());
+            member_path
+                .and_then(|path| monorepo.get_scope_for_file(path))
+                .is_some()
+        })?;
+
+        let group = monorepo.get_group_for_file(path.strip_prefix(root).ok()?.to_str()?)?;
+        root.join(group).to_str().map(|group| group.to_owned())
+    }
+}
@@ -0,0 +1,273 @@
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::commitlint::Commitlint;
+
+static CONFIG_FILE: &str = ".commitscopes.toml";
+
+static PACKAGE_JSON_CONFIG_KEY: &str = "commitScopes";
+
+/// Repository level settings, read from `.commitscopes.toml` at the repo root
+/// or else from the `commitScopes` key of the root package.json. Every
+/// setting is optional so a missing file behaves like an empty one.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    /// Forces a backend from `MONOREPO_DETECTORS` instead of detecting it.
+    pub backend: Option<String>,
+    /// Backends rooted at subdirectories of a polyglot repo. When set these
+    /// are used instead of detecting a single backend at the repo root.
+    pub monorepos: Vec<MonorepoConfig>,
+    /// Glob to scope mappings, taking precedence over the detected backend.
+    pub scopes: Vec<ScopeMapping>,
+    /// `.gitignore` style patterns of staged files that never contribute
+    /// scopes, e.g. lockfiles or changelogs.
+    pub ignore: Vec<String>,
+    pub bazel: BazelConfig,
+    pub python: PythonConfig,
+    pub codeowners: CodeownersConfig,
+    pub aliases: AliasesConfig,
+    pub collapse: CollapseConfig,
+    pub groups: GroupsConfig,
+    pub root: RootConfig,
+    pub weight: WeightConfig,
+    /// Rules inferring the commit type from the staged changes, replacing the
+    /// default ones when set. The type falls back to `chore`.
+    pub type_rules: Option<Vec<TypeRule>>,
+    /// Commit types accepted in user messages, defaulting to `COMMIT_TYPES`.
+    pub types: Option<CommitTypes>,
+    /// What to do with scopes typed in a `-m` message header.
+    pub user_scopes: UserScopes,
+    /// Rules from the commitlint config of the repo, if any.
+    #[serde(skip)]
+    pub commitlint: Option<Commitlint>,
+}
+
+#[derive(Deserialize)]
+pub struct MonorepoConfig {
+    /// Directory relative to the repo root the backend is rooted at.
+    pub path: String,
+    /// Backend to use, detected from the directory contents when omitted.
+    pub backend: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct ScopeMapping {
+    /// Glob matched against repo relative paths, e.g. `infra/terraform/**`.
+    pub pattern: String,
+    pub scope: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct BazelConfig {
+    /// Number of leading package path segments kept before the last one is
+    /// used as the scope, e.g. `2` scopes `//services/payments/api` as `payments`.
+    pub depth: Option<usize>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct PythonConfig {
+    /// Globs restricting which `pyproject.toml` directories are packages,
+    /// following the same format as lerna.json `packages`.
+    pub packages: Option<Vec<String>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct CodeownersConfig {
+    /// Scope to use for an owner handle or email, e.g. `"@acme/payments-team" = "payments"`.
+    /// Owners without an entry are scoped by their handle.
+    pub owners: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct AliasesConfig {
+    pub org_prefix: OrgPrefix,
+    /// Orgs stripped with `org_prefix = "strip-listed"`, e.g. `["@acme"]`.
+    pub strip_orgs: Vec<String>,
+    /// Exact renames, keyed by the package name with or without its org prefix.
+    pub rename: HashMap<String, String>,
+    /// Regex rewrites applied in order to scopes that are not renamed.
+    pub rewrite: Vec<RewriteRule>,
+}
+
+/// How the `@org/` prefix of npm package names is handled. Names that
+/// collide once stripped, like `@web/utils` and `@api/utils`, are reported.
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum OrgPrefix {
+    /// `@org/name` is scoped as `name`
+    #[default]
+    Strip,
+    /// `@org/name` is scoped as `@org/name`
+    Keep,
+    /// `@org/name` is scoped as `org-name`
+    Join,
+    /// `@org/name` is scoped as `name` for orgs in `strip_orgs`, others are kept
+    StripListed,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct CollapseConfig {
+    /// Scopes are collapsed when more than this many are found, never when unset.
+    pub threshold: Option<usize>,
+    pub strategy: CollapseStrategy,
+    /// Trailer key listing every collapsed scope in the commit body, e.g. `Scopes`.
+    pub trailer: Option<String>,
+}
+
+/// What the scope list is replaced with once it is collapsed.
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum CollapseStrategy {
+    /// `chore(*):`
+    #[default]
+    Wildcard,
+    /// The directory containing every staged path, e.g. `chore(packages):`,
+    /// falling back to the wildcard when they share none.
+    Group,
+    /// `chore:`
+    Omit,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct GroupsConfig {
+    /// Reports scopes by directory group, taken from the static prefix of the
+    /// package globs (`libs` for `libs/*`). Scopes are listed as is when unset.
+    pub mode: Option<GroupMode>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum GroupMode {
+    /// The group replaces the scopes when every change falls within it
+    Group,
+    /// Every group with several changed packages is replaced by the group,
+    /// others are prefixed with it, e.g. `apps/web,libs`
+    Mixed,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct RootConfig {
+    /// Scope for staged files outside any package, like `package.json` or
+    /// `.github/` at the repo root, e.g. `repo`.
+    pub scope: Option<String>,
+    pub mix: RootScopeMix,
+}
+
+/// When the root scope is used alongside package scopes.
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum RootScopeMix {
+    /// Only when no package is changed, e.g. `chore(repo):`
+    #[default]
+    Alone,
+    /// Whenever a file outside any package is changed, e.g. `chore(app1,repo):`
+    Always,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct WeightConfig {
+    /// Orders scopes by the lines changed in their staged files, largest
+    /// first, instead of by name.
+    pub by_lines: bool,
+    /// Drops scopes with less than this share of the lines changed, e.g.
+    /// `0.05`. Only used with `by_lines`.
+    pub min_share: Option<f64>,
+}
+
+/// How scopes typed by the user are merged with the detected ones.
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum UserScopes {
+    /// The user's scopes are left as typed
+    #[default]
+    Keep,
+    /// Detected scopes are added after the user's ones
+    Union,
+    /// Detected scopes replace the user's ones
+    Replace,
+    /// The user's scopes are kept, with a warning for those not detected
+    Warn,
+}
+
+/// Either a list of types or a commitlint `type-enum` rule such as
+/// `[2, "always", ["feat", "fix"]]`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum CommitTypes {
+    List(Vec<String>),
+    TypeEnum(u8, String, Vec<String>),
+}
+
+#[derive(Deserialize)]
+pub struct TypeRule {
+    #[serde(rename = "type")]
+    pub commit_type: String,
+    /// `.gitignore` style patterns every staged file must match, e.g. `docs/`.
+    pub paths: Vec<String>,
+    /// Matches when any added file matches `paths` instead.
+    #[serde(default)]
+    pub new_files: bool,
+    /// Regex an added line of the matched files must match, e.g. `^export `.
+    pub added_line: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct RewriteRule {
+    /// Regex matched against the scope, e.g. `-(app|lib)$`.
+    pub pattern: String,
+    /// Replacement, which can refer to capture groups as `$1`.
+    #[serde(default)]
+    pub replace: String,
+}
+
+impl Config {
+    pub fn load(repo_root: &Path) -> Config {
+        let mut config = Config::load_config_file(repo_root)
+            .or_else(|| Config::load_package_json(repo_root))
+            .unwrap_or_default();
+
+        config.commitlint = Commitlint::load(repo_root);
+
+        config
+    }
+
+    fn load_config_file(repo_root: &Path) -> Option<Config> {
+        let contents = fs::read_to_string(repo_root.join(CONFIG_FILE)).ok()?;
+
+        match toml::from_str(&contents) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::warn!("failed to parse {}, ignoring it: {}\n", CONFIG_FILE, e);
+                None
+            }
+        }
+    }
+
+    fn load_package_json(repo_root: &Path) -> Option<Config> {
+        let package_json_file = fs::File::open(repo_root.join("package.json")).ok()?;
+        let mut package_json: serde_json::Value =
+            serde_json::from_reader(package_json_file).ok()?;
+        let config = package_json.get_mut(PACKAGE_JSON_CONFIG_KEY)?.take();
+
+        match serde_json::from_value(config) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::warn!(
+                    "failed to parse package.json {}, ignoring it: {}\n",
+                    PACKAGE_JSON_CONFIG_KEY,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
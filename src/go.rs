@@ -0,0 +1,116 @@
+use std::path::{Component, Path, PathBuf};
+use std::{collections::HashMap, fs};
+
+use crate::config::Config;
+use crate::manifest::{find_manifest_dirs, find_nearest_manifest};
+use crate::Monorepo;
+
+/// Directories the go tool does not look for modules in.
+static IGNORED_DIRS: [&str; 2] = ["vendor", "testdata"];
+
+pub struct GoMonorepo {
+    repo_root: PathBuf,
+    /// Module directories relative to the repo root mapped to their scope.
+    modules: HashMap<PathBuf, String>,
+}
+
+impl Monorepo for GoMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let module_dirs = match fs::read_to_string(repo_root.join("go.work")) {
+            Ok(go_work) => GoMonorepo::parse_go_work_use_directives(&go_work),
+            Err(_) => find_manifest_dirs(&repo_root, "go.mod", &IGNORED_DIRS),
+        };
+
+        let modules: HashMap<PathBuf, String> = module_dirs
+            .into_iter()
+            .filter(|dir| dir.components().count() > 0)
+            .filter_map(|dir| {
+                let go_mod = fs::read_to_string(repo_root.join(&dir).join("go.mod")).ok()?;
+                let module_path = GoMonorepo::parse_module_directive(&go_mod)?;
+
+                Some((dir, GoMonorepo::module_scope(&module_path)?))
+            })
+            .collect();
+
+        if modules.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(GoMonorepo { repo_root, modules }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        find_nearest_manifest(&self.repo_root, path, "go.mod", |dir_relative, _| {
+            self.modules.get(dir_relative).cloned()
+        })
+    }
+}
+
+impl GoMonorepo {
+    /// Collects the directories listed by `use` directives, in both the
+    /// single line `use ./dir` and the block `use ( ... )` forms.
+    fn parse_go_work_use_directives(go_work: &str) -> Vec<PathBuf> {
+        let mut dirs = vec![];
+        let mut in_use_block = false;
+
+        for line in go_work.lines() {
+            let line = GoMonorepo::strip_comment(line);
+
+            if in_use_block {
+                if line == ")" {
+                    in_use_block = false;
+                } else if !line.is_empty() {
+                    dirs.push(GoMonorepo::normalize_dir(line));
+                }
+            } else if let Some(rest) = line.strip_prefix("use") {
+                let rest = rest.trim();
+
+                if rest == "(" {
+                    in_use_block = true;
+                } else if !rest.is_empty() {
+                    dirs.push(GoMonorepo::normalize_dir(rest));
+                }
+            }
+        }
+
+        dirs
+    }
+
+    fn parse_module_directive(go_mod: &str) -> Option<String> {
+        go_mod.lines().find_map(|line| {
+            let module_path = GoMonorepo::strip_comment(line).strip_prefix("module")?;
+
+            Some(module_path.trim().trim_matches('"').to_owned())
+        })
+    }
+
+    /// Uses the last segment of the module path, skipping major version
+    /// suffixes so `example.com/payments/v2` is scoped as `payments`.
+    fn module_scope(module_path: &str) -> Option<String> {
+        module_path
+            .rsplit('/')
+            .find(|segment| !GoMonorepo::is_major_version_suffix(segment))
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| segment.to_owned())
+    }
+
+    fn is_major_version_suffix(segment: &str) -> bool {
+        segment.len() > 1
+            && segment.starts_with('v')
+            && segment[1..].chars().all(|c| c.is_ascii_digit())
+    }
+
+    fn strip_comment(line: &str) -> &str {
+        match line.find("//") {
+            Some(index) => line[..index].trim(),
+            None => line.trim(),
+        }
+    }
+
+    fn normalize_dir(dir: &str) -> PathBuf {
+        Path::new(dir.trim_matches('"'))
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect()
+    }
+}
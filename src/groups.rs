@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use crate::aliases::ScopeAliases;
+use crate::config::GroupMode;
+use crate::Monorepo;
+
+/// Maps every scope to the staged changes it comes from. With a `mode` the
+/// scopes are reported by the directory group of their package.
+pub fn get_scoped_changes(
+    mode: Option<GroupMode>,
+    monorepo: &dyn Monorepo,
+    aliases: &ScopeAliases,
+    staged_changes: Vec<String>,
+) -> BTreeMap<String, Vec<String>> {
+    let scoped: Vec<(String, String, Option<String>)> = staged_changes
+        .into_iter()
+        .filter_map(|path| {
+            let scope = monorepo.get_scope_for_file(&path)?;
+            let group = mode.and_then(|_| monorepo.get_group_for_file(&path));
+            Some((path, scope, group))
+        })
+        .collect();
+
+    let aliased = aliases.alias_all(
+        scoped
+            .iter()
+            .map(|(_, scope, _)| scope.to_owned())
+            .collect(),
+    );
+    let mut groups: BTreeMap<Option<String>, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+
+    for (path, scope, group) in scoped {
+        if let Some(alias) = aliased.get(&scope) {
+            groups
+                .entry(group)
+                .or_default()
+                .entry(alias.to_owned())
+                .or_default()
+                .push(path);
+        }
+    }
+
+    log::info!("found scope groups {:?}\n", groups);
+
+    let is_single_group = matches!(groups.keys().collect::<Vec<_>>().as_slice(), [Some(_)]);
+    let mut scoped_changes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for (group, scopes) in groups {
+        match (mode, group) {
+            (Some(GroupMode::Group), Some(group)) if is_single_group => {
+                scoped_changes.insert(group, scopes.into_values().flatten().collect());
+            }
+            (Some(GroupMode::Mixed), Some(group)) if scopes.len() > 1 => {
+                scoped_changes.insert(group, scopes.into_values().flatten().collect());
+            }
+            (Some(GroupMode::Mixed), Some(group)) => {
+                for (scope, paths) in scopes {
+                    scoped_changes.insert(format!("{}/{}", group, scope), paths);
+                }
+            }
+            _ => {
+                for (scope, paths) in scopes {
+                    scoped_changes.entry(scope).or_default().extend(paths);
+                }
+            }
+        }
+    }
+
+    scoped_changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::Config;
+    use std::path::{Path, PathBuf};
+    use test_utilities::test_each;
+
+    /// Scopes `group/package/...` paths as `package`, other paths as their
+    /// first segment without a group.
+    struct MockMonorepo {}
+
+    impl Monorepo for MockMonorepo {
+        fn new(_repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+            Some(Box::new(MockMonorepo {}))
+        }
+
+        fn get_scope_for_file(&self, path: &str) -> Option<String> {
+            let segments: Vec<&str> = path.split('/').collect();
+            segments
+                .get(1)
+                .or_else(|| segments.first())
+                .map(|s| s.to_string())
+        }
+
+        fn get_group_for_file(&self, path: &str) -> Option<String> {
+            let dir = Path::new(path).parent()?.parent()?;
+            dir.to_str()
+                .filter(|dir| !dir.is_empty())
+                .map(|dir| dir.to_owned())
+        }
+    }
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            staged_changes: Vec<&'a str>,
+            expected_scopes: Vec<&'a str>,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                description: "reports the scopes without a mode",
+                config: "",
+                staged_changes: vec!["libs/ui/index.ts", "libs/utils/index.ts"],
+                expected_scopes: vec!["ui", "utils"],
+            },
+            TestCase {
+                description: "reports the group when every change falls within it",
+                config: "[groups]\nmode = \"group\"",
+                staged_changes: vec!["libs/ui/index.ts", "libs/utils/index.ts"],
+                expected_scopes: vec!["libs"],
+            },
+            TestCase {
+                description: "reports the group for a single package",
+                config: "[groups]\nmode = \"group\"",
+                staged_changes: vec!["libs/ui/index.ts"],
+                expected_scopes: vec!["libs"],
+            },
+            TestCase {
+                description: "reports the scopes when changes span several groups",
+                config: "[groups]\nmode = \"group\"",
+                staged_changes: vec!["apps/web/index.ts", "libs/ui/index.ts"],
+                expected_scopes: vec!["ui", "web"],
+            },
+            TestCase {
+                description: "reports the scopes when some changes have no group",
+                config: "[groups]\nmode = \"group\"",
+                staged_changes: vec!["docs", "libs/ui/index.ts"],
+                expected_scopes: vec!["docs", "ui"],
+            },
+            TestCase {
+                description: "mixes groups and prefixed scopes",
+                config: "[groups]\nmode = \"mixed\"",
+                staged_changes: vec![
+                    "apps/web/index.ts",
+                    "libs/ui/index.ts",
+                    "libs/utils/index.ts",
+                    "docs",
+                ],
+                expected_scopes: vec!["apps/web", "docs", "libs"],
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let config: Config = toml::from_str(tc.config).unwrap();
+            let monorepo = MockMonorepo::new(PathBuf::new(), &config).unwrap();
+
+            let scoped_changes = get_scoped_changes(
+                config.groups.mode,
+                monorepo.as_ref(),
+                &ScopeAliases::new(&config),
+                tc.staged_changes.iter().map(|s| s.to_string()).collect(),
+            );
+
+            assert_eq!(
+                scoped_changes.keys().collect::<Vec<_>>(),
+                tc.expected_scopes
+            );
+        })
+    }
+}
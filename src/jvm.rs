@@ -0,0 +1,190 @@
+use std::path::{Component, Path, PathBuf};
+use std::{collections::HashMap, fs};
+
+use crate::config::Config;
+use crate::Monorepo;
+
+static GRADLE_SETTINGS_FILES: [&str; 2] = ["settings.gradle.kts", "settings.gradle"];
+
+/// Maven reactor builds and Gradle multi-project builds, scoped by the
+/// module `artifactId` or the Gradle project name respectively.
+pub struct JvmMonorepo {
+    /// Module directories relative to the repo root mapped to their scope.
+    modules: HashMap<PathBuf, String>,
+}
+
+impl Monorepo for JvmMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let mut modules = HashMap::new();
+
+        if repo_root.join("pom.xml").exists() {
+            JvmMonorepo::collect_maven_modules(&repo_root, Path::new(""), &mut modules);
+        } else if let Some(settings) = GRADLE_SETTINGS_FILES
+            .iter()
+            .find_map(|name| fs::read_to_string(repo_root.join(name)).ok())
+        {
+            modules = JvmMonorepo::parse_gradle_includes(&settings);
+        }
+
+        if modules.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(JvmMonorepo { modules }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        Path::new(path)
+            .ancestors()
+            .skip(1)
+            .find_map(|dir| self.modules.get(dir).cloned())
+    }
+}
+
+impl JvmMonorepo {
+    /// Reads the `<modules>` of the pom.xml in `dir`, recursing into each
+    /// module so nested aggregators are supported.
+    fn collect_maven_modules(repo_root: &Path, dir: &Path, modules: &mut HashMap<PathBuf, String>) {
+        let contents = match fs::read_to_string(repo_root.join(dir).join("pom.xml")) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+
+        let pom = match roxmltree::Document::parse(&contents) {
+            Ok(pom) => pom,
+            Err(e) => {
+                log::info!("failed to parse {:#?} {}\n", dir.join("pom.xml"), e);
+                return;
+            }
+        };
+
+        let project = pom.root_element();
+
+        if dir.components().count() > 0 {
+            if let Some(artifact_id) = JvmMonorepo::child_text(project, "artifactId") {
+                modules.insert(dir.to_path_buf(), artifact_id);
+            }
+        }
+
+        let module_dirs: Vec<PathBuf> = project
+            .children()
+            .filter(|node| node.has_tag_name("modules"))
+            .flat_map(|node| node.children())
+            .filter(|node| node.has_tag_name("module"))
+            .filter_map(|node| node.text())
+            .filter_map(|module| {
+                let module = Path::new(module.trim());
+                // modules may point at the pom.xml itself instead of its directory
+                let module_dir = match module.extension() {
+                    Some(extension) if extension == "xml" => module.parent()?,
+                    _ => module,
+                };
+
+                JvmMonorepo::normalize_path(&dir.join(module_dir))
+            })
+            .collect();
+
+        for module_dir in module_dirs {
+            if !modules.contains_key(&module_dir) {
+                JvmMonorepo::collect_maven_modules(repo_root, &module_dir, modules);
+            }
+        }
+    }
+
+    fn child_text(node: roxmltree::Node, tag_name: &str) -> Option<String> {
+        node.children()
+            .find(|child| child.has_tag_name(tag_name))
+            .and_then(|child| child.text())
+            .map(|text| text.trim().to_owned())
+    }
+
+    /// Collects the project paths of every `include` statement, in both the
+    /// groovy `include ':a', ':b'` and kotlin `include(":a", ":b")` forms.
+    /// Arguments may span several lines, up to the closing `)` or, without
+    /// parentheses, for as long as lines end with a `,`.
+    /// A project path like `:libs:core` lives in `libs/core` and is named `core`.
+    fn parse_gradle_includes(settings: &str) -> HashMap<PathBuf, String> {
+        let mut modules = HashMap::new();
+        let mut lines = settings.lines().map(|line| line.trim());
+
+        while let Some(line) = lines.next() {
+            let mut arguments = match line.strip_prefix("include") {
+                Some(arguments) if arguments.starts_with(['(', ' ']) => arguments.to_owned(),
+                _ => continue,
+            };
+
+            let is_parenthesized = arguments.trim_start().starts_with('(');
+
+            let is_complete = |arguments: &str| {
+                if is_parenthesized {
+                    arguments.contains(')')
+                } else {
+                    !arguments.ends_with(',')
+                }
+            };
+
+            while !is_complete(&arguments) {
+                match lines.next() {
+                    Some(next_line) => {
+                        arguments.push(' ');
+                        arguments.push_str(next_line);
+                    }
+                    None => break,
+                }
+            }
+
+            for project_path in JvmMonorepo::quoted_strings(&arguments) {
+                let segments: Vec<&str> = project_path
+                    .split(':')
+                    .filter(|segment| !segment.is_empty())
+                    .collect();
+
+                if let Some(name) = segments.last() {
+                    modules.insert(segments.iter().collect(), name.to_string());
+                }
+            }
+        }
+
+        modules
+    }
+
+    fn quoted_strings(text: &str) -> Vec<&str> {
+        let mut strings = vec![];
+        let mut rest = text;
+
+        while let Some(start) = rest.find(['\'', '"']) {
+            let quote = rest[start..].chars().next().unwrap();
+            let after_quote = &rest[start + 1..];
+
+            match after_quote.find(quote) {
+                Some(end) => {
+                    strings.push(&after_quote[..end]);
+                    rest = &after_quote[end + 1..];
+                }
+                None => break,
+            }
+        }
+
+        strings
+    }
+
+    /// Resolves `.` and `..` segments, rejecting paths outside the repo.
+    fn normalize_path(path: &Path) -> Option<PathBuf> {
+        let mut normalized = PathBuf::new();
+
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normalized.pop() {
+                        return None;
+                    }
+                }
+                Component::Normal(segment) => normalized.push(segment),
+                _ => return None,
+            }
+        }
+
+        Some(normalized)
+    }
+}
@@ -1,36 +1,186 @@
+use aliases::ScopeAliases;
+use bazel::BazelMonorepo;
+use cargo_workspace::CargoMonorepo;
+use codeowners::CodeownersMonorepo;
+use commit_type::CommitTypeRules;
+use composite::{CompositeMonorepo, COMPOSITE_BACKEND};
+use config::{Config, RootScopeMix};
 use git2::{Repository, RepositoryOpenFlags, Status, StatusOptions};
+use go::GoMonorepo;
+use groups::get_scoped_changes;
+use jvm::JvmMonorepo;
 use lerna::LernaMonorepo;
+use mappings::{MappedMonorepo, MAPPINGS_BACKEND};
+use nx::NxMonorepo;
+use path_patterns::PathPatterns;
+use pnpm::PnpmMonorepo;
+use python::PythonMonorepo;
+use rush::RushMonorepo;
+use staged::{get_staged_diff, StagedChange};
+use std::cell::OnceCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use weights::order_by_weight;
+use workspaces::WorkspacesMonorepo;
 
+mod aliases;
+mod bazel;
+mod cargo_workspace;
+mod codeowners;
+mod commit_header;
+mod commit_type;
+mod commitlint;
+mod composite;
+mod go;
+mod groups;
+mod jvm;
 mod lerna;
+mod manifest;
+mod mappings;
+mod nx;
+mod package_json;
+mod path_patterns;
+mod pnpm;
+mod python;
+mod rush;
+mod staged;
+mod weights;
+mod workspaces;
 
+pub mod config;
 pub mod logger;
 pub mod prepare_commit_msg;
 
 pub trait Monorepo {
-    fn new(repo_path: PathBuf) -> Option<Box<dyn Monorepo>>
+    #[allow(clippy::new_ret_no_self)]
+    fn new(repo_path: PathBuf, config: &Config) -> Option<Box<dyn Monorepo>>
     where
         Self: Sized;
-    fn get_commit_scopes(&self, statuses: Vec<String>) -> Vec<String>;
+    fn get_scope_for_file(&self, path: &str) -> Option<String>;
+
+    /// Directory group of the package containing `path`, e.g. `libs` for a
+    /// package matched by `libs/*`. Only backends driven by package globs
+    /// report groups.
+    fn get_group_for_file(&self, _path: &str) -> Option<String> {
+        None
+    }
+}
+
+/// Builds a backend for the repo, returning `None` when it does not apply.
+pub type MonorepoDetector = fn(PathBuf, &Config) -> Option<Box<dyn Monorepo>>;
+
+/// Supported backends in detection order, the first one that applies wins.
+pub static MONOREPO_DETECTORS: [(&str, MonorepoDetector); 11] = [
+    ("lerna", LernaMonorepo::new),
+    ("workspaces", WorkspacesMonorepo::new),
+    ("pnpm", PnpmMonorepo::new),
+    ("rush", RushMonorepo::new),
+    ("cargo", CargoMonorepo::new),
+    ("nx", NxMonorepo::new),
+    ("go", GoMonorepo::new),
+    ("bazel", BazelMonorepo::new),
+    ("jvm", JvmMonorepo::new),
+    ("python", PythonMonorepo::new),
+    ("codeowners", CodeownersMonorepo::new),
+];
+
+/// Picks the backend for `repo_root`, either the forced `backend` or the
+/// first detector in `MONOREPO_DETECTORS` that applies.
+pub fn detect_monorepo(
+    repo_root: &Path,
+    backend: Option<&str>,
+    config: &Config,
+) -> Option<(&'static str, Box<dyn Monorepo>)> {
+    if let Some(backend) = backend {
+        let detector = MONOREPO_DETECTORS.iter().find(|(name, _)| *name == backend);
+
+        return match detector {
+            Some((name, detect)) => detect(repo_root.to_path_buf(), config).map(|m| (*name, m)),
+            None => {
+                let names: Vec<&str> = MONOREPO_DETECTORS.iter().map(|(name, _)| *name).collect();
+                log::warn!(
+                    "unknown monorepo backend {} in config, expected one of {}\n",
+                    backend,
+                    names.join(", ")
+                );
+                None
+            }
+        };
+    }
+
+    MONOREPO_DETECTORS.iter().find_map(|(name, detect)| {
+        detect(repo_root.to_path_buf(), config).map(|monorepo| (*name, monorepo))
+    })
 }
 
 pub trait CommitScopeFinder {
     fn get_commit_scopes(&self) -> Vec<String>;
+
+    /// Repo relative paths of the staged changes the scopes come from.
+    fn get_staged_paths(&self) -> Vec<String> {
+        vec![]
+    }
+
+    /// Commit type inferred from the staged changes, if any.
+    fn get_commit_type(&self) -> Option<String> {
+        None
+    }
 }
 
 pub struct DefaultScopeFinder {
     repo: Repository,
+    config: Config,
     monorepo: Option<Box<dyn Monorepo>>,
+    backend: Option<String>,
+    aliases: ScopeAliases,
+    ignored: PathPatterns,
+    type_rules: CommitTypeRules,
+    /// Read once as both the scope weights and the commit type need it.
+    staged_diff: OnceCell<Vec<StagedChange>>,
 }
 
-impl<'a> CommitScopeFinder for DefaultScopeFinder {
+impl CommitScopeFinder for DefaultScopeFinder {
     fn get_commit_scopes(&self) -> Vec<String> {
+        log::info!("using monorepo backend {:?}\n", &self.backend);
+
+        let staged_changes = self.get_staged_paths();
+
+        let mut scoped_changes = match &self.monorepo {
+            Some(monorepo) => get_scoped_changes(
+                self.config.groups.mode,
+                monorepo.as_ref(),
+                &self.aliases,
+                staged_changes.clone(),
+            ),
+            None => BTreeMap::new(),
+        };
+
+        let scoped_paths: HashSet<&String> = scoped_changes.values().flatten().collect();
+        let unscoped_changes: Vec<String> = staged_changes
+            .iter()
+            .filter(|path| !scoped_paths.contains(path))
+            .cloned()
+            .collect();
+
+        self.add_root_scope(&mut scoped_changes, unscoped_changes);
+
+        if self.config.weight.by_lines {
+            let line_counts: HashMap<String, usize> = self
+                .get_staged_diff()
+                .iter()
+                .map(|change| (change.path.clone(), change.lines_changed))
+                .collect();
+            return order_by_weight(scoped_changes, &line_counts, self.config.weight.min_share);
+        }
+
+        scoped_changes.into_keys().collect()
+    }
+
+    fn get_staged_paths(&self) -> Vec<String> {
         let mut status_opts = StatusOptions::new();
 
         let statuses = self.repo.statuses(Some(&mut status_opts)).unwrap();
 
-        log::info!("has monorepo {}\n", &self.monorepo.is_some());
-
         // TODO: test only index files are considered to return scopes
         let index_statuses: [Status; 5] = [
             Status::INDEX_NEW,
@@ -40,23 +190,33 @@ impl<'a> CommitScopeFinder for DefaultScopeFinder {
             Status::INDEX_TYPECHANGE,
         ];
 
-        let staged_changes: Vec<String> = statuses
+        statuses
             .iter()
             .filter(|entry| {
                 index_statuses
                     .iter()
                     .any(|s| entry.status().contains(s.to_owned()))
             })
-            .map(|entry| {
-                return entry.path().unwrap().to_owned();
+            .map(|entry| entry.path().unwrap().to_owned())
+            .filter(|path| {
+                let is_ignored = self.ignored.is_match(path);
+
+                if is_ignored {
+                    log::info!("ignoring staged entry {}\n", path);
+                }
+
+                !is_ignored
             })
-            .collect();
+            .collect()
+    }
 
-        if let Some(monorepo) = &self.monorepo {
-            return monorepo.get_commit_scopes(staged_changes);
+    fn get_commit_type(&self) -> Option<String> {
+        // repos without any scope are left alone, as before types were inferred
+        if self.monorepo.is_none() && self.config.root.scope.is_none() {
+            return None;
         }
 
-        return vec![];
+        self.type_rules.infer(self.get_staged_diff())
     }
 }
 
@@ -67,14 +227,97 @@ impl DefaultScopeFinder {
             RepositoryOpenFlags::CROSS_FS,
             Vec::<String>::new(),
         )
-        .expect(&format!(
-            "failed to load git repo from path given {}",
-            repo_path_str.to_str().unwrap()
-        ));
+        .unwrap_or_else(|_| {
+            panic!(
+                "failed to load git repo from path given {}",
+                repo_path_str.to_str().unwrap()
+            )
+        });
 
         let repo_root = repo.path().parent().unwrap().to_path_buf();
-        let monorepo = LernaMonorepo::new(repo_root);
+        let config = Config::load(&repo_root);
+        let detected = CompositeMonorepo::new(repo_root.clone(), &config)
+            .map(|monorepo| (COMPOSITE_BACKEND, monorepo))
+            .or_else(|| detect_monorepo(&repo_root, config.backend.as_deref(), &config));
+
+        let (mut backend, mut monorepo) = match detected {
+            Some((backend, monorepo)) => (Some(backend.to_owned()), Some(monorepo)),
+            None => (None, None),
+        };
+
+        // scope mappings from the config take precedence over the detected backend
+        if !config.scopes.is_empty() {
+            let mapped = MappedMonorepo::with_fallback(&config, monorepo.take());
+
+            backend = Some(match backend {
+                Some(backend) => format!("{}+{}", MAPPINGS_BACKEND, backend),
+                None => MAPPINGS_BACKEND.to_owned(),
+            });
+            monorepo = Some(Box::new(mapped));
+        }
+
+        let aliases = ScopeAliases::new(&config);
+        let ignored = PathPatterns::new(&repo_root, &config.ignore)
+            .expect("invalid pattern found in ignore config");
+        let type_rules = CommitTypeRules::new(&repo_root, &config);
+
+        DefaultScopeFinder {
+            repo,
+            config,
+            monorepo,
+            backend,
+            aliases,
+            ignored,
+            type_rules,
+            staged_diff: OnceCell::new(),
+        }
+    }
+
+    /// Name of the backend in `MONOREPO_DETECTORS` used for this repo, or
+    /// `composite` when several are configured. Prefixed with `mappings+`
+    /// when scope mappings are configured on top of it.
+    pub fn backend_name(&self) -> Option<&str> {
+        self.backend.as_deref()
+    }
+
+    /// Staged changes with their diff, leaving out ignored files.
+    fn get_staged_diff(&self) -> &[StagedChange] {
+        self.staged_diff.get_or_init(|| {
+            get_staged_diff(&self.repo)
+                .into_iter()
+                .filter(|change| !self.ignored.is_match(&change.path))
+                .collect()
+        })
+    }
+
+    /// Adds the configured `root.scope` for the staged files not scoped by
+    /// the backend, following the `root.mix` rule.
+    fn add_root_scope(
+        &self,
+        scoped_changes: &mut BTreeMap<String, Vec<String>>,
+        unscoped_changes: Vec<String>,
+    ) {
+        let root_scope = match &self.config.root.scope {
+            Some(root_scope) if !unscoped_changes.is_empty() => root_scope,
+            _ => return,
+        };
+
+        let add_root_scope = match self.config.root.mix {
+            RootScopeMix::Alone => scoped_changes.is_empty(),
+            RootScopeMix::Always => true,
+        };
+
+        if add_root_scope {
+            log::info!("adding root scope {}\n", root_scope);
+            scoped_changes
+                .entry(root_scope.to_owned())
+                .or_default()
+                .extend(unscoped_changes);
+        }
+    }
 
-        DefaultScopeFinder { repo, monorepo }
+    /// Settings loaded from the repo, see `Config::load`.
+    pub fn config(&self) -> &Config {
+        &self.config
     }
 }
@@ -34,13 +34,29 @@ impl Logger {
     }
 }
 
+impl Default for Logger {
+    fn default() -> Self {
+        Logger::new()
+    }
+}
+
 impl log::Log for Logger {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        env::var(DEBUG_LOG_FILE).is_ok() && metadata.level() <= Level::Info
+        metadata.level() <= Level::Warn
+            || (env::var(DEBUG_LOG_FILE).is_ok() && metadata.level() <= Level::Info)
     }
 
     fn log(&self, record: &Record) {
         if self.enabled(record.metadata()) {
+            // warnings and errors are meant for the user so they are shown even without DEBUG_LOG
+            if record.level() <= Level::Warn {
+                eprint!(
+                    "{}: {}",
+                    record.level().as_str().to_ascii_lowercase(),
+                    record.args()
+                );
+            }
+
             let msg = format!("{}-{} {}", record.level(), record.target(), record.args());
             if let Some(writer) = &self.writer {
                 writer
@@ -0,0 +1,140 @@
+use globset::{GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use std::path::{Component, Path, PathBuf};
+
+/// Package directory globs. Patterns prefixed with `!` exclude directories
+/// that would otherwise be matched by the remaining patterns.
+pub struct PackageGlobs {
+    included: GlobSet,
+    excluded: GlobSet,
+    /// Included patterns paired with their static prefix, e.g. `libs` for
+    /// `libs/*`, skipping patterns without one.
+    groups: Vec<(GlobMatcher, String)>,
+}
+
+impl PackageGlobs {
+    pub fn is_match(&self, dir: &Path) -> bool {
+        self.included.is_match(dir) && !self.excluded.is_match(dir)
+    }
+
+    /// Static prefix of the first pattern matching the package directory.
+    pub fn group_for_dir(&self, dir: &Path) -> Option<String> {
+        if self.excluded.is_match(dir) {
+            return None;
+        }
+
+        self.groups
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(dir))
+            .map(|(_, group)| group.to_owned())
+    }
+}
+
+pub fn build_packages_globs(patterns: &[String]) -> Result<PackageGlobs, globset::Error> {
+    let mut included = GlobSetBuilder::new();
+    let mut excluded = GlobSetBuilder::new();
+    let mut groups = vec![];
+
+    for pattern in patterns.iter() {
+        if let Some(negated) = pattern.strip_prefix('!') {
+            excluded.add(GlobBuilder::new(negated).literal_separator(true).build()?);
+        } else {
+            let glob = GlobBuilder::new(pattern).literal_separator(true).build()?;
+
+            if let Some(group) = static_prefix(pattern) {
+                groups.push((glob.compile_matcher(), group));
+            }
+
+            included.add(glob);
+        }
+    }
+
+    Ok(PackageGlobs {
+        included: included.build()?,
+        excluded: excluded.build()?,
+        groups,
+    })
+}
+
+/// Leading path segments of a glob without any wildcard, e.g. `libs/shared`
+/// for `./libs/shared/*`.
+fn static_prefix(pattern: &str) -> Option<String> {
+    let segments: Vec<&str> = Path::new(pattern)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(segment) => segment.to_str(),
+            _ => None,
+        })
+        .take_while(|segment| !segment.contains(['*', '?', '[', '{']))
+        .collect();
+
+    // a pattern without wildcards is the package itself, not a group of them
+    if segments.is_empty() || segments.len() == Path::new(pattern).components().count() {
+        return None;
+    }
+
+    Some(segments.join("/"))
+}
+
+/// Walks up from a staged entry towards the repo root, calling `read` with
+/// the repo relative directory and manifest path for every directory that
+/// contains `manifest_name`. The first value returned by `read` wins, files
+/// at the repo root never resolve to a manifest.
+pub fn find_nearest_manifest<T, F>(
+    repo_root: &Path,
+    entry: &str,
+    manifest_name: &str,
+    mut read: F,
+) -> Option<T>
+where
+    F: FnMut(&Path, &Path) -> Option<T>,
+{
+    let abs_path = repo_root.join(entry);
+
+    for dir in abs_path.ancestors() {
+        if dir.eq(repo_root) {
+            return None;
+        }
+
+        let manifest_path = dir.join(manifest_name);
+
+        if manifest_path.exists() {
+            let dir_relative = dir.strip_prefix(repo_root).unwrap();
+
+            if let Some(value) = read(dir_relative, &manifest_path) {
+                return Some(value);
+            }
+        }
+    }
+
+    None
+}
+
+/// Lists the repo relative directories containing `manifest_name`. Hidden
+/// and git ignored directories are skipped, as well as any directory named
+/// in `ignored_dirs`.
+pub fn find_manifest_dirs(
+    repo_root: &Path,
+    manifest_name: &str,
+    ignored_dirs: &'static [&'static str],
+) -> Vec<PathBuf> {
+    WalkBuilder::new(repo_root)
+        .filter_entry(move |entry| {
+            !ignored_dirs
+                .iter()
+                .any(|dir| entry.file_name().to_str() == Some(dir))
+        })
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() == manifest_name)
+        .map(|entry| {
+            entry
+                .path()
+                .parent()
+                .unwrap()
+                .strip_prefix(repo_root)
+                .unwrap()
+                .to_path_buf()
+        })
+        .collect()
+}
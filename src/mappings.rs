@@ -0,0 +1,69 @@
+use globset::{Glob, GlobBuilder, GlobMatcher};
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::Monorepo;
+
+pub static MAPPINGS_BACKEND: &str = "mappings";
+
+/// Scopes files from the `scopes` glob to scope mappings in the config. The
+/// first matching mapping wins, files matching none are handed to the
+/// detected backend, if any.
+pub struct MappedMonorepo {
+    mappings: Vec<(GlobMatcher, String)>,
+    fallback: Option<Box<dyn Monorepo>>,
+}
+
+impl Monorepo for MappedMonorepo {
+    fn new(_repo_root: PathBuf, config: &Config) -> Option<Box<dyn Monorepo>> {
+        if config.scopes.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(MappedMonorepo::with_fallback(config, None)))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        let mapped_scope = self
+            .mappings
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(path))
+            .map(|(_, scope)| scope.to_owned());
+
+        match (&mapped_scope, &self.fallback) {
+            (None, Some(fallback)) => fallback.get_scope_for_file(path),
+            _ => mapped_scope,
+        }
+    }
+
+    fn get_group_for_file(&self, path: &str) -> Option<String> {
+        let is_mapped = self
+            .mappings
+            .iter()
+            .any(|(matcher, _)| matcher.is_match(path));
+
+        match &self.fallback {
+            Some(fallback) if !is_mapped => fallback.get_group_for_file(path),
+            _ => None,
+        }
+    }
+}
+
+impl MappedMonorepo {
+    pub fn with_fallback(config: &Config, fallback: Option<Box<dyn Monorepo>>) -> MappedMonorepo {
+        let mappings = config
+            .scopes
+            .iter()
+            .map(|mapping| {
+                let glob: Glob = GlobBuilder::new(&mapping.pattern)
+                    .literal_separator(true)
+                    .build()
+                    .expect("invalid glob found in scopes config");
+
+                (glob.compile_matcher(), mapping.scope.to_owned())
+            })
+            .collect();
+
+        MappedMonorepo { mappings, fallback }
+    }
+}
@@ -0,0 +1,119 @@
+use serde::Deserialize;
+use std::cmp::Reverse;
+use std::path::{Component, Path, PathBuf};
+use std::{collections::HashMap, fs::File};
+
+use crate::config::Config;
+use crate::manifest::find_manifest_dirs;
+use crate::Monorepo;
+
+/// Entries of the `projects` map are either the project root or, in older
+/// workspaces, the full project configuration.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WorkspaceProject {
+    Root(String),
+    Config { root: String },
+}
+
+#[derive(Deserialize)]
+struct WorkspaceJSON {
+    projects: HashMap<String, WorkspaceProject>,
+}
+
+#[derive(Deserialize)]
+struct ProjectJSON {
+    name: Option<String>,
+}
+
+static WORKSPACE_CONFIG_FILES: [&str; 2] = ["workspace.json", "angular.json"];
+
+pub struct NxMonorepo {
+    /// Project roots relative to the repo root, longest first so nested
+    /// projects take precedence over their parents.
+    projects: Vec<(PathBuf, String)>,
+}
+
+impl Monorepo for NxMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let workspace_projects = NxMonorepo::parse_workspace_config(&repo_root);
+
+        if workspace_projects.is_none() && !repo_root.join("nx.json").exists() {
+            return None;
+        }
+
+        let mut projects = workspace_projects.unwrap_or_default();
+        projects.extend(NxMonorepo::find_project_json_files(&repo_root));
+        projects.sort_by_key(|(root, _)| Reverse(root.components().count()));
+
+        Some(Box::new(NxMonorepo { projects }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        let path = Path::new(path);
+
+        self.projects
+            .iter()
+            .find(|(root, _)| path.starts_with(root))
+            .map(|(_, name)| name.to_owned())
+    }
+}
+
+impl NxMonorepo {
+    fn parse_workspace_config(repo_root: &Path) -> Option<Vec<(PathBuf, String)>> {
+        let config_file = WORKSPACE_CONFIG_FILES
+            .iter()
+            .find_map(|name| File::open(repo_root.join(name)).ok())?;
+        let workspace: WorkspaceJSON = serde_json::from_reader(config_file).ok()?;
+
+        let projects = workspace
+            .projects
+            .into_iter()
+            .map(|(name, project)| {
+                let root = match project {
+                    WorkspaceProject::Root(root) => root,
+                    WorkspaceProject::Config { root } => root,
+                };
+
+                (NxMonorepo::normalize_root(&root), name)
+            })
+            .collect();
+
+        Some(projects)
+    }
+
+    fn find_project_json_files(repo_root: &Path) -> Vec<(PathBuf, String)> {
+        let mut projects = vec![];
+
+        for project_root in find_manifest_dirs(repo_root, "project.json", &[]) {
+            let project_json_path = repo_root.join(&project_root).join("project.json");
+
+            if let Ok(project_json_file) = File::open(project_json_path) {
+                if let Ok(project_json) =
+                    serde_json::from_reader::<_, ProjectJSON>(project_json_file)
+                {
+                    // nx falls back to the directory name for unnamed projects
+                    let name = project_json.name.or_else(|| {
+                        project_root
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .map(|name| name.to_owned())
+                    });
+
+                    if let Some(name) = name {
+                        projects.push((project_root, name));
+                    }
+                }
+            }
+        }
+
+        projects
+    }
+
+    fn normalize_root(root: &str) -> PathBuf {
+        Path::new(root)
+            .components()
+            .filter(|component| !matches!(component, Component::CurDir))
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Result;
+use std::fs::File;
+use std::path::PathBuf;
+
+use crate::manifest::{find_nearest_manifest, PackageGlobs};
+
+#[derive(Serialize, Deserialize)]
+struct PackageJSON {
+    name: String,
+}
+
+/// Resolves staged files to the name of the nearest `package.json` whose
+/// directory matches one of the configured package globs.
+pub struct PackageLocator {
+    repo_root: PathBuf,
+    packages_globs: PackageGlobs,
+}
+
+impl PackageLocator {
+    pub fn new(repo_root: PathBuf, packages_globs: PackageGlobs) -> PackageLocator {
+        PackageLocator {
+            repo_root,
+            packages_globs,
+        }
+    }
+
+    pub fn get_package_name_for_file(&self, entry: &str) -> Option<String> {
+        self.find_package(entry).map(|(_, name)| name)
+    }
+
+    pub fn get_package_group_for_file(&self, entry: &str) -> Option<String> {
+        let (dir, _) = self.find_package(entry)?;
+        self.packages_globs.group_for_dir(&dir)
+    }
+
+    fn find_package(&self, entry: &str) -> Option<(PathBuf, String)> {
+        find_nearest_manifest(
+            &self.repo_root,
+            entry,
+            "package.json",
+            |dir_relative, package_json_path| {
+                let package_json_file = File::open(package_json_path).ok()?;
+                let package_json_result: Result<PackageJSON> =
+                    serde_json::from_reader(package_json_file);
+                let package_json = package_json_result.ok()?;
+
+                if !self.packages_globs.is_match(dir_relative) {
+                    return None;
+                }
+
+                Some((dir_relative.to_path_buf(), package_json.name))
+            },
+        )
+    }
+}
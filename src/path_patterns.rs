@@ -0,0 +1,87 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::Path;
+
+/// Repo relative path patterns following the `.gitignore` format, so `*.md`
+/// matches at any depth and `docs/` matches every file within `docs`.
+pub struct PathPatterns {
+    gitignore: Gitignore,
+}
+
+impl PathPatterns {
+    pub fn new(repo_root: &Path, patterns: &[String]) -> Result<PathPatterns, ignore::Error> {
+        let mut builder = GitignoreBuilder::new(repo_root);
+
+        for pattern in patterns.iter() {
+            builder.add_line(None, pattern)?;
+        }
+
+        Ok(PathPatterns {
+            gitignore: builder.build()?,
+        })
+    }
+
+    pub fn is_match(&self, path: &str) -> bool {
+        self.gitignore
+            .matched_path_or_any_parents(path, false)
+            .is_ignore()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utilities::test_each;
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            patterns: Vec<&'a str>,
+            path: &'a str,
+            expected_match: bool,
+        }
+
+        let test_cases = vec![
+            TestCase {
+                description: "matches nothing without patterns",
+                patterns: vec![],
+                path: "package-lock.json",
+                expected_match: false,
+            },
+            TestCase {
+                description: "matches patterns without a slash at any depth",
+                patterns: vec!["package-lock.json"],
+                path: "packages/package1/package-lock.json",
+                expected_match: true,
+            },
+            TestCase {
+                description: "matches patterns with a leading slash at the repo root only",
+                patterns: vec!["/CHANGELOG.md"],
+                path: "packages/package1/CHANGELOG.md",
+                expected_match: false,
+            },
+            TestCase {
+                description: "matches files within matched directories",
+                patterns: vec!["__snapshots__/"],
+                path: "packages/package1/src/__snapshots__/app.test.ts.snap",
+                expected_match: true,
+            },
+            TestCase {
+                description: "does not match paths re-included by a negated pattern",
+                patterns: vec!["*.md", "!README.md"],
+                path: "packages/package1/README.md",
+                expected_match: false,
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let patterns: Vec<String> = tc.patterns.iter().map(|p| p.to_string()).collect();
+            let path_patterns = PathPatterns::new(Path::new("/repo"), &patterns).unwrap();
+
+            assert_eq!(path_patterns.is_match(tc.path), tc.expected_match);
+        })
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use std::{fs::File, path::Path, path::PathBuf};
+
+use crate::config::Config;
+use crate::manifest::build_packages_globs;
+use crate::package_json::PackageLocator;
+use crate::Monorepo;
+
+#[derive(Deserialize)]
+struct PnpmWorkspaceConfig {
+    #[serde(default)]
+    packages: Vec<String>,
+}
+
+pub struct PnpmMonorepo {
+    packages: PackageLocator,
+}
+
+impl Monorepo for PnpmMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let config = PnpmMonorepo::parse_pnpm_workspace(&repo_root)?;
+        let globs = build_packages_globs(&config.packages)
+            .expect("invalid glob found in pnpm-workspace.yaml packages");
+
+        Some(Box::new(PnpmMonorepo {
+            packages: PackageLocator::new(repo_root, globs),
+        }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        self.packages.get_package_name_for_file(path)
+    }
+
+    fn get_group_for_file(&self, path: &str) -> Option<String> {
+        self.packages.get_package_group_for_file(path)
+    }
+}
+
+impl PnpmMonorepo {
+    fn parse_pnpm_workspace(repo_path: &Path) -> Option<PnpmWorkspaceConfig> {
+        let config_file = File::open(repo_path.join("pnpm-workspace.yaml")).ok()?;
+
+        match serde_yaml::from_reader(config_file) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::info!("failed to parse pnpm-workspace.yaml {}\n", e);
+                None
+            }
+        }
+    }
+}
@@ -1,26 +1,43 @@
 use std::io::{Read, Seek, SeekFrom, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::commit_header::CommitHeader;
+use crate::config::{CollapseStrategy, CommitTypes, Config, UserScopes};
 use crate::CommitScopeFinder;
 
-pub static COMMIT_TYPES: [&'static str; 11] = [
+pub static COMMIT_TYPES: [&str; 11] = [
     "build", "ci", "chore", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
 ];
 
+static DEFAULT_COMMIT_TYPE: &str = "chore";
+
+static WILDCARD_SCOPE: &str = "*";
+
 pub struct PrepareCommitMessage<'a> {
     pub commit_msg_file: &'a Path,
     pub commit_source: Option<&'a str>,
     pub commit_msg_generator: &'a dyn CommitScopeFinder,
+    pub config: &'a Config,
 }
 
 impl<'a> PrepareCommitMessage<'a> {
     pub fn update_commit(&self) -> std::io::Result<()> {
-        let scopes = self.commit_msg_generator.get_commit_scopes();
+        let mut scopes = self.commit_msg_generator.get_commit_scopes();
 
-        if scopes.len() == 0 {
-            return Ok(());
+        if let Some(commitlint) = &self.config.commitlint {
+            scopes = commitlint.apply_scope_rules(scopes);
         }
 
+        // types are only inferred for generated messages, and used when
+        // accepted by the repo
+        let commit_type = match self.commit_source {
+            None | Some("template") => self
+                .commit_msg_generator
+                .get_commit_type()
+                .filter(|commit_type| self.commit_types().contains(commit_type)),
+            Some(_) => None,
+        };
+
         let mut commit_msg_file = std::fs::OpenOptions::new()
             .read(true)
             .write(true)
@@ -29,17 +46,42 @@ impl<'a> PrepareCommitMessage<'a> {
         let mut original_commit_msg = String::new();
         commit_msg_file.read_to_string(&mut original_commit_msg)?;
 
+        // checked first as typing a scope matters most when no package is staged
+        if let (Some("message"), UserScopes::Warn) = (self.commit_source, self.config.user_scopes) {
+            for scope in unmatched_user_scopes(&original_commit_msg, &scopes) {
+                log::warn!("scope {} does not match any staged package\n", scope);
+            }
+        }
+
+        // files outside packages have no scope but can still get a type
+        if scopes.is_empty() && commit_type.is_none() {
+            return Ok(());
+        }
+
         let mut new_commit_msg: Option<String> = None;
+        let (scopes, collapsed_scopes) = self.collapse_scopes(scopes);
 
         match self.commit_source {
             Some("message") => {
                 new_commit_msg = self.handle_message_commit_source(&original_commit_msg, scopes)
             }
-            None | Some("template") => new_commit_msg = self.handle_default(&original_commit_msg, scopes),
+            None | Some("template") => {
+                new_commit_msg = self.handle_default(&original_commit_msg, commit_type, scopes)
+            }
             Some(_) => {}
         }
 
-        if let Some(msg) = new_commit_msg {
+        if let Some(mut msg) = new_commit_msg {
+            if let (Some(trailer), Some(collapsed_scopes)) =
+                (&self.config.collapse.trailer, collapsed_scopes)
+            {
+                let trailer = format!("{}: {}", trailer, collapsed_scopes.join(", "));
+                msg = append_trailer(&msg, &trailer);
+            }
+
+            self.check_header_length(&msg);
+
+            commit_msg_file.set_len(0)?;
             commit_msg_file.seek(SeekFrom::Start(0))?;
             commit_msg_file.write_all(msg.as_bytes())?;
         }
@@ -47,48 +89,226 @@ impl<'a> PrepareCommitMessage<'a> {
         Ok(())
     }
 
-    fn handle_default(&self, commit_msg: &'a str, scopes: Vec<String>) -> Option<String> {
-        Some(format!("chore({}):\n{}", scopes.join(","), commit_msg))
+    /// Warns when the header is longer than the commitlint `header-max-length`.
+    fn check_header_length(&self, commit_msg: &str) {
+        let max_length = match &self.config.commitlint {
+            Some(commitlint) => commitlint.header_max_length(),
+            None => None,
+        };
+
+        let header = commit_msg.lines().next().unwrap_or_default();
+
+        if let Some(max_length) = max_length {
+            if header.chars().count() > max_length {
+                log::warn!(
+                    "commit header is longer than the {} characters allowed by commitlint header-max-length\n",
+                    max_length
+                );
+            }
+        }
+    }
+
+    /// Replaces the scopes following `collapse.strategy` when there are more
+    /// than `collapse.threshold`, also returning the scopes it replaced.
+    fn collapse_scopes(&self, scopes: Vec<String>) -> (Vec<String>, Option<Vec<String>>) {
+        let collapse = &self.config.collapse;
+
+        match collapse.threshold {
+            Some(threshold) if scopes.len() > threshold => {}
+            _ => return (scopes, None),
+        }
+
+        let collapsed = match collapse.strategy {
+            CollapseStrategy::Wildcard => vec![WILDCARD_SCOPE.to_owned()],
+            CollapseStrategy::Group => vec![self
+                .find_common_group()
+                .unwrap_or_else(|| WILDCARD_SCOPE.to_owned())],
+            CollapseStrategy::Omit => vec![],
+        };
+
+        log::info!("collapsed {} scopes to {:?}\n", scopes.len(), collapsed);
+
+        (collapsed, Some(scopes))
+    }
+
+    /// Name of the deepest directory containing every staged path.
+    fn find_common_group(&self) -> Option<String> {
+        let mut common_dir: Option<PathBuf> = None;
+
+        for path in self.commit_msg_generator.get_staged_paths() {
+            let dir = Path::new(&path).parent()?;
+
+            common_dir = Some(match common_dir {
+                None => dir.to_path_buf(),
+                Some(common_dir) => common_dir
+                    .components()
+                    .zip(dir.components())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect(),
+            });
+        }
+
+        common_dir?
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.to_owned())
     }
 
+    /// Types from the `types` config or else the commitlint `type-enum` rule,
+    /// following its semantics: level `0` disables the rule and `never` lists
+    /// the types that are not accepted.
+    fn commit_types(&self) -> Vec<String> {
+        let default_types = COMMIT_TYPES.iter().map(|ct| ct.to_string());
+        let type_enum = self
+            .config
+            .commitlint
+            .as_ref()
+            .and_then(|commitlint| commitlint.type_enum());
+
+        match self.config.types.as_ref().or(type_enum.as_ref()) {
+            None | Some(CommitTypes::TypeEnum(0, _, _)) => default_types.collect(),
+            Some(CommitTypes::TypeEnum(_, when, types)) if when == "never" => {
+                default_types.filter(|ct| !types.contains(ct)).collect()
+            }
+            Some(CommitTypes::List(types)) | Some(CommitTypes::TypeEnum(_, _, types)) => {
+                types.to_owned()
+            }
+        }
+    }
+
+    fn handle_default(
+        &self,
+        commit_msg: &'a str,
+        commit_type: Option<String>,
+        scopes: Vec<String>,
+    ) -> Option<String> {
+        // falls back to chore, or the first accepted type when chore is not
+        let commit_type = match commit_type {
+            Some(commit_type) => commit_type,
+            None => {
+                let commit_types = self.commit_types();
+
+                if commit_types.iter().any(|ct| ct == DEFAULT_COMMIT_TYPE) {
+                    DEFAULT_COMMIT_TYPE.to_owned()
+                } else {
+                    commit_types.into_iter().next()?
+                }
+            }
+        };
+
+        Some(format!(
+            "{}{}:\n{}",
+            commit_type,
+            format_scopes(&scopes),
+            commit_msg
+        ))
+    }
+
+    /// Adds the scopes to the header of the message, merging them with scopes
+    /// typed by the user following `user_scopes`.
     fn handle_message_commit_source(
         &self,
         commit_msg: &'a str,
         scopes: Vec<String>,
     ) -> Option<String> {
-        if let Some(first_line) = commit_msg.lines().next() {
-            if let Some(commit_type) = COMMIT_TYPES
-                .iter()
-                .find(|ct| first_line.starts_with(&format!("{}:", ct)))
-            {
-                return Some(commit_msg.replace(
-                    &format!("{}:", commit_type),
-                    &format!("{}({}):", commit_type, scopes.join(",")),
-                ));
+        let (first_line, rest) = match commit_msg.find('\n') {
+            Some(idx) => commit_msg.split_at(idx),
+            None => (commit_msg, ""),
+        };
+
+        let mut header = CommitHeader::parse(first_line)?;
+
+        if !self.commit_types().contains(&header.commit_type) {
+            return None;
+        }
+
+        if header.scopes.is_empty() {
+            header.scopes = scopes;
+        } else {
+            match self.config.user_scopes {
+                // unmatched scopes were already reported when warning
+                UserScopes::Keep | UserScopes::Warn => return None,
+                UserScopes::Union => {
+                    // the wildcard would widen the scopes typed by the user
+                    for scope in scopes.into_iter().filter(|scope| scope != WILDCARD_SCOPE) {
+                        if !header.scopes.contains(&scope) {
+                            header.scopes.push(scope);
+                        }
+                    }
+                }
+                UserScopes::Replace => header.scopes = scopes,
             }
         }
 
-        None
+        Some(format!("{}{}", header.render(), rest))
+    }
+}
+
+/// Scopes typed in the message header that are not among `detected_scopes`.
+fn unmatched_user_scopes(commit_msg: &str, detected_scopes: &[String]) -> Vec<String> {
+    let first_line = commit_msg.lines().next().unwrap_or_default();
+
+    match CommitHeader::parse(first_line) {
+        Some(header) => header
+            .scopes
+            .into_iter()
+            .filter(|scope| !detected_scopes.contains(scope))
+            .collect(),
+        None => vec![],
+    }
+}
+
+fn format_scopes(scopes: &[String]) -> String {
+    if scopes.is_empty() {
+        return String::new();
     }
+
+    format!("({})", scopes.join(","))
+}
+
+/// Adds `trailer` as the last paragraph of the message, before the comments
+/// git appends to it.
+fn append_trailer(commit_msg: &str, trailer: &str) -> String {
+    let comments_start = std::iter::once(0)
+        .chain(commit_msg.match_indices('\n').map(|(i, _)| i + 1))
+        .find(|&i| commit_msg[i..].starts_with('#'))
+        .unwrap_or(commit_msg.len());
+    let (message, comments) = commit_msg.split_at(comments_start);
+
+    format!("{}\n\n{}\n{}", message.trim_end(), trailer, comments)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use core::panic;
     use std::io::SeekFrom;
     use tempfile::NamedTempFile;
     use test_utilities::test_each;
 
     struct MockCommitScopeFinder {
         scopes: Vec<String>,
+        staged_paths: Vec<String>,
+        commit_type: Option<String>,
     }
 
     impl CommitScopeFinder for MockCommitScopeFinder {
         fn get_commit_scopes(&self) -> Vec<String> {
             self.scopes.clone()
         }
+
+        fn get_staged_paths(&self) -> Vec<String> {
+            self.staged_paths.clone()
+        }
+
+        fn get_commit_type(&self) -> Option<String> {
+            self.commit_type.clone()
+        }
+    }
+
+    fn to_strings(strs: &[&str]) -> Vec<String> {
+        strs.iter().map(|s| s.to_string()).collect()
     }
 
     fn create_tmp_git_commit_file(contents: &str) -> NamedTempFile {
@@ -158,6 +378,34 @@ mod tests {
                 commit_msg_contents: "docs: commit message with type specified already",
                 expected_commit: "docs(package): commit message with type specified already",
             },
+            TestCase {
+                description: "adds scopes to a breaking change",
+                commit_source: Some("message"),
+                scopes: vec!["package"],
+                commit_msg_contents: "feat!: drop node 12",
+                expected_commit: "feat(package)!: drop node 12",
+            },
+            TestCase {
+                description: "keeps scopes typed by the user",
+                commit_source: Some("message"),
+                scopes: vec!["package"],
+                commit_msg_contents: "feat(ui)!: new theme",
+                expected_commit: "feat(ui)!: new theme",
+            },
+            TestCase {
+                description: "only rewrites the header of the message",
+                commit_source: Some("message"),
+                scopes: vec!["package"],
+                commit_msg_contents: "docs: update readme\n\nsee docs: for details\n\nRefs: #123\n",
+                expected_commit: "docs(package): update readme\n\nsee docs: for details\n\nRefs: #123\n",
+            },
+            TestCase {
+                description: "does not do anything when the message has no type",
+                commit_source: Some("message"),
+                scopes: vec!["package"],
+                commit_msg_contents: "update the docs: readme",
+                expected_commit: "update the docs: readme",
+            },
             TestCase {
                 description:
                     "adds generated commit message to the beginning of commit message file",
@@ -187,8 +435,104 @@ mod tests {
                 commit_msg_file: tmp_file.path(),
                 commit_source: tc.commit_source,
                 commit_msg_generator: &MockCommitScopeFinder {
-                    scopes: tc.scopes.iter().map(|s| s.to_string()).collect(),
+                    scopes: to_strings(&tc.scopes),
+                    staged_paths: vec![],
+                    commit_type: None,
+                },
+                config: &Config::default(),
+            };
+
+            pcm.update_commit().unwrap();
+
+            assert_eq!(read_git_commit_file(tmp_file), tc.expected_commit);
+        });
+    }
+
+    #[test]
+    fn collapse_tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            commit_msg_contents: &'a str,
+            commit_source: Option<&'a str>,
+            scopes: Vec<&'a str>,
+            staged_paths: Vec<&'a str>,
+            expected_commit: &'a str,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                description: "does not collapse scopes up to the threshold",
+                config: "[collapse]\nthreshold = 2",
+                commit_source: None,
+                scopes: vec!["app1", "app2"],
+                staged_paths: vec![],
+                commit_msg_contents: "",
+                expected_commit: "chore(app1,app2):\n",
+            },
+            TestCase {
+                description: "collapses scopes above the threshold to a wildcard",
+                config: "[collapse]\nthreshold = 2",
+                commit_source: None,
+                scopes: vec!["app1", "app2", "app3"],
+                staged_paths: vec![],
+                commit_msg_contents: "",
+                expected_commit: "chore(*):\n",
+            },
+            TestCase {
+                description: "collapses scopes to the directory containing the staged paths",
+                config: "[collapse]\nthreshold = 1\nstrategy = \"group\"",
+                commit_source: Some("message"),
+                scopes: vec!["ui", "utils"],
+                staged_paths: vec!["libs/shared/ui/index.ts", "libs/shared/utils/src/index.ts"],
+                commit_msg_contents: "fix: something",
+                expected_commit: "fix(shared): something",
+            },
+            TestCase {
+                description: "collapses scopes to a wildcard when staged paths share no directory",
+                config: "[collapse]\nthreshold = 1\nstrategy = \"group\"",
+                commit_source: None,
+                scopes: vec!["ui", "utils"],
+                staged_paths: vec!["libs/ui/index.ts", "package.json"],
+                commit_msg_contents: "",
+                expected_commit: "chore(*):\n",
+            },
+            TestCase {
+                description: "omits collapsed scopes and lists them in a trailer",
+                config: "[collapse]\nthreshold = 1\nstrategy = \"omit\"\ntrailer = \"Scopes\"",
+                commit_source: None,
+                scopes: vec!["app1", "app2"],
+                staged_paths: vec![],
+                commit_msg_contents: "\n# Please enter the commit message\n",
+                expected_commit:
+                    "chore:\n\nScopes: app1, app2\n# Please enter the commit message\n",
+            },
+            TestCase {
+                description: "lists collapsed scopes in a trailer after the user message",
+                config: "[collapse]\nthreshold = 1\ntrailer = \"Scopes\"",
+                commit_source: Some("message"),
+                scopes: vec!["app1", "app2"],
+                staged_paths: vec![],
+                commit_msg_contents: "feat: something\n\nsome body\n",
+                expected_commit: "feat(*): something\n\nsome body\n\nScopes: app1, app2\n",
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let tmp_file = create_tmp_git_commit_file(tc.commit_msg_contents);
+            let config: Config = toml::from_str(tc.config).unwrap();
+
+            let pcm = PrepareCommitMessage {
+                commit_msg_file: tmp_file.path(),
+                commit_source: tc.commit_source,
+                commit_msg_generator: &MockCommitScopeFinder {
+                    scopes: to_strings(&tc.scopes),
+                    staged_paths: to_strings(&tc.staged_paths),
+                    commit_type: None,
                 },
+                config: &config,
             };
 
             pcm.update_commit().unwrap();
@@ -196,4 +540,243 @@ mod tests {
             assert_eq!(read_git_commit_file(tmp_file), tc.expected_commit);
         });
     }
+
+    #[test]
+    fn it_uses_the_inferred_commit_type() {
+        let tmp_file = create_tmp_git_commit_file("\n# Please enter the commit message\n");
+
+        let pcm = PrepareCommitMessage {
+            commit_msg_file: tmp_file.path(),
+            commit_source: None,
+            commit_msg_generator: &MockCommitScopeFinder {
+                scopes: to_strings(&["package"]),
+                staged_paths: vec![],
+                commit_type: Some("docs".to_owned()),
+            },
+            config: &Config::default(),
+        };
+
+        pcm.update_commit().unwrap();
+
+        assert_eq!(
+            read_git_commit_file(tmp_file),
+            "docs(package):\n\n# Please enter the commit message\n"
+        );
+    }
+
+    #[test]
+    fn commit_types_tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            commit_msg_contents: &'a str,
+            expected_commit: &'a str,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                description: "accepts the default types",
+                config: "{}",
+                commit_msg_contents: "refactor: something",
+                expected_commit: "refactor(package): something",
+            },
+            TestCase {
+                description: "accepts types from a list",
+                config: r#"{ "types": ["deps", "release"] }"#,
+                commit_msg_contents: "deps: bump react",
+                expected_commit: "deps(package): bump react",
+            },
+            TestCase {
+                description: "ignores types missing from a list",
+                config: r#"{ "types": ["deps", "release"] }"#,
+                commit_msg_contents: "feat: something",
+                expected_commit: "feat: something",
+            },
+            TestCase {
+                description: "accepts types from a commitlint type-enum rule",
+                config: r#"{ "types": [2, "always", ["wip", "security"]] }"#,
+                commit_msg_contents: "security: patch xss",
+                expected_commit: "security(package): patch xss",
+            },
+            TestCase {
+                description: "accepts the default types when the type-enum rule is disabled",
+                config: r#"{ "types": [0, "always", ["wip"]] }"#,
+                commit_msg_contents: "fix: something",
+                expected_commit: "fix(package): something",
+            },
+            TestCase {
+                description: "ignores the types listed in a never type-enum rule",
+                config: r#"{ "types": [2, "never", ["chore"]] }"#,
+                commit_msg_contents: "chore: something",
+                expected_commit: "chore: something",
+            },
+            TestCase {
+                description: "accepts types from a type-enum rule in TOML",
+                config: r#"types = [2, "always", ["wip", "security"]]"#,
+                commit_msg_contents: "wip: something",
+                expected_commit: "wip(package): something",
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let tmp_file = create_tmp_git_commit_file(tc.commit_msg_contents);
+            // configs are given as in package.json, or else as in .commitscopes.toml
+            let config: Config = serde_json::from_str(tc.config)
+                .ok()
+                .unwrap_or_else(|| toml::from_str(tc.config).unwrap());
+
+            let pcm = PrepareCommitMessage {
+                commit_msg_file: tmp_file.path(),
+                commit_source: Some("message"),
+                commit_msg_generator: &MockCommitScopeFinder {
+                    scopes: to_strings(&["package"]),
+                    staged_paths: vec![],
+                    commit_type: None,
+                },
+                config: &config,
+            };
+
+            pcm.update_commit().unwrap();
+
+            assert_eq!(read_git_commit_file(tmp_file), tc.expected_commit);
+        });
+    }
+
+    #[test]
+    fn default_commit_type_tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            expected_commit: &'a str,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                description: "falls back to chore when it is accepted",
+                config: r#"{ "types": ["fix", "chore"] }"#,
+                expected_commit: "chore(package):\n",
+            },
+            TestCase {
+                description: "falls back to the first accepted type otherwise",
+                config: r#"{ "types": [2, "always", ["deps", "fix"]] }"#,
+                expected_commit: "deps(package):\n",
+            },
+            TestCase {
+                description: "falls back to the first default type when chore is never accepted",
+                config: r#"{ "types": [2, "never", ["chore"]] }"#,
+                expected_commit: "build(package):\n",
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let tmp_file = create_tmp_git_commit_file("");
+            let config: Config = serde_json::from_str(tc.config).unwrap();
+
+            let pcm = PrepareCommitMessage {
+                commit_msg_file: tmp_file.path(),
+                commit_source: None,
+                commit_msg_generator: &MockCommitScopeFinder {
+                    scopes: to_strings(&["package"]),
+                    staged_paths: vec![],
+                    commit_type: None,
+                },
+                config: &config,
+            };
+
+            pcm.update_commit().unwrap();
+
+            assert_eq!(read_git_commit_file(tmp_file), tc.expected_commit);
+        });
+    }
+
+    #[test]
+    fn user_scopes_tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            config: &'a str,
+            commit_msg_contents: &'a str,
+            expected_commit: &'a str,
+        }
+
+        let test_cases: Vec<TestCase> = vec![
+            TestCase {
+                description: "keeps the user's scopes by default",
+                config: "",
+                commit_msg_contents: "fix(auth): handle expired tokens",
+                expected_commit: "fix(auth): handle expired tokens",
+            },
+            TestCase {
+                description: "adds detected scopes after the user's ones",
+                config: "user_scopes = \"union\"",
+                commit_msg_contents: "fix(auth)!: handle expired tokens\n\nsome body",
+                expected_commit: "fix(auth,api)!: handle expired tokens\n\nsome body",
+            },
+            TestCase {
+                description: "replaces the user's scopes",
+                config: "user_scopes = \"replace\"",
+                commit_msg_contents: "fix(web): handle expired tokens",
+                expected_commit: "fix(api,auth): handle expired tokens",
+            },
+            TestCase {
+                description: "keeps the user's scopes when warning",
+                config: "user_scopes = \"warn\"",
+                commit_msg_contents: "fix(web): handle expired tokens",
+                expected_commit: "fix(web): handle expired tokens",
+            },
+            TestCase {
+                description: "adds detected scopes when the user typed none",
+                config: "user_scopes = \"replace\"",
+                commit_msg_contents: "fix: handle expired tokens",
+                expected_commit: "fix(api,auth): handle expired tokens",
+            },
+            TestCase {
+                description: "does not add the wildcard of collapsed scopes to the user's ones",
+                config: "user_scopes = \"union\"\n\n[collapse]\nthreshold = 1",
+                commit_msg_contents: "fix(auth): handle expired tokens",
+                expected_commit: "fix(auth): handle expired tokens",
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let tmp_file = create_tmp_git_commit_file(tc.commit_msg_contents);
+            let config: Config = toml::from_str(tc.config).unwrap();
+
+            let pcm = PrepareCommitMessage {
+                commit_msg_file: tmp_file.path(),
+                commit_source: Some("message"),
+                commit_msg_generator: &MockCommitScopeFinder {
+                    scopes: to_strings(&["api", "auth"]),
+                    staged_paths: vec![],
+                    commit_type: None,
+                },
+                config: &config,
+            };
+
+            pcm.update_commit().unwrap();
+
+            assert_eq!(read_git_commit_file(tmp_file), tc.expected_commit);
+        });
+    }
+
+    #[test]
+    fn it_finds_user_scopes_matching_no_staged_package() {
+        assert_eq!(
+            unmatched_user_scopes(
+                "fix(web,api): something\n\nsome body",
+                &to_strings(&["api"])
+            ),
+            vec!["web"]
+        );
+        assert_eq!(
+            unmatched_user_scopes("fix(web): something", &[]),
+            vec!["web"]
+        );
+        assert!(unmatched_user_scopes("fix: something", &[]).is_empty());
+    }
 }
@@ -0,0 +1,111 @@
+use serde::Deserialize;
+use std::{collections::HashMap, fs, path::Path, path::PathBuf};
+
+use crate::config::Config;
+use crate::manifest::{build_packages_globs, find_manifest_dirs, find_nearest_manifest};
+use crate::Monorepo;
+
+#[derive(Deserialize)]
+struct PyProject {
+    project: Option<PyProjectName>,
+    tool: Option<PyProjectTool>,
+}
+
+#[derive(Deserialize)]
+struct PyProjectTool {
+    poetry: Option<PyProjectName>,
+}
+
+#[derive(Deserialize)]
+struct PyProjectName {
+    name: Option<String>,
+}
+
+pub struct PythonMonorepo {
+    repo_root: PathBuf,
+    /// Package directories relative to the repo root mapped to their scope.
+    packages: HashMap<PathBuf, String>,
+}
+
+impl Monorepo for PythonMonorepo {
+    fn new(repo_root: PathBuf, config: &Config) -> Option<Box<dyn Monorepo>> {
+        let packages_globs = config.python.packages.as_ref().map(|patterns| {
+            build_packages_globs(patterns)
+                .expect("invalid glob found in .commitscopes.toml python packages")
+        });
+
+        let packages: HashMap<PathBuf, String> =
+            find_manifest_dirs(&repo_root, "pyproject.toml", &[])
+                .into_iter()
+                .filter(|dir| dir.components().count() > 0)
+                .filter(|dir| match &packages_globs {
+                    Some(globs) => globs.is_match(dir),
+                    None => true,
+                })
+                .filter_map(|dir| {
+                    let name = PythonMonorepo::parse_project_name(
+                        &repo_root.join(&dir).join("pyproject.toml"),
+                    )?;
+
+                    Some((dir, PythonMonorepo::normalize_name(&name)))
+                })
+                .collect();
+
+        if packages.is_empty() {
+            return None;
+        }
+
+        Some(Box::new(PythonMonorepo {
+            repo_root,
+            packages,
+        }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        find_nearest_manifest(
+            &self.repo_root,
+            path,
+            "pyproject.toml",
+            |dir_relative, _| self.packages.get(dir_relative).cloned(),
+        )
+    }
+}
+
+impl PythonMonorepo {
+    /// Reads the PEP 621 `[project]` name, falling back to `[tool.poetry]`.
+    fn parse_project_name(pyproject_path: &Path) -> Option<String> {
+        let contents = fs::read_to_string(pyproject_path).ok()?;
+
+        let pyproject: PyProject = match toml::from_str(&contents) {
+            Ok(pyproject) => pyproject,
+            Err(e) => {
+                log::info!("failed to parse {:#?} {}\n", pyproject_path, e);
+                return None;
+            }
+        };
+
+        let PyProject { project, tool } = pyproject;
+
+        project
+            .and_then(|project| project.name)
+            .or_else(|| tool?.poetry?.name)
+    }
+
+    /// Normalizes a distribution name as described in PEP 503, lowercasing
+    /// it and collapsing runs of `-`, `_` and `.` into a single `-`.
+    fn normalize_name(name: &str) -> String {
+        let mut normalized = String::new();
+
+        for c in name.trim().chars() {
+            if c == '-' || c == '_' || c == '.' {
+                if !normalized.ends_with('-') {
+                    normalized.push('-');
+                }
+            } else {
+                normalized.extend(c.to_lowercase());
+            }
+        }
+
+        normalized
+    }
+}
@@ -0,0 +1,114 @@
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::config::Config;
+use crate::Monorepo;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RushProject {
+    package_name: String,
+    project_folder: String,
+}
+
+#[derive(Deserialize)]
+struct RushConfig {
+    #[serde(default)]
+    projects: Vec<RushProject>,
+}
+
+/// Rush lists every project explicitly, so staged files are resolved from
+/// the rush.json mapping alone without reading any package.json.
+pub struct RushMonorepo {
+    /// Project folders relative to the repo root mapped to their package name.
+    projects: HashMap<PathBuf, String>,
+}
+
+impl Monorepo for RushMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let config = RushMonorepo::parse_rush_config(&repo_root)?;
+
+        let projects = config
+            .projects
+            .into_iter()
+            .map(|project| {
+                let folder: PathBuf = Path::new(&project.project_folder)
+                    .components()
+                    .filter(|component| !matches!(component, Component::CurDir))
+                    .collect();
+
+                (folder, project.package_name)
+            })
+            .collect();
+
+        Some(Box::new(RushMonorepo { projects }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        Path::new(path)
+            .ancestors()
+            .skip(1)
+            .find_map(|dir| self.projects.get(dir).cloned())
+    }
+}
+
+impl RushMonorepo {
+    fn parse_rush_config(repo_path: &Path) -> Option<RushConfig> {
+        let contents = fs::read_to_string(repo_path.join("rush.json")).ok()?;
+
+        match serde_json::from_str(&RushMonorepo::strip_comments(&contents)) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                log::info!("failed to parse rush.json {}\n", e);
+                None
+            }
+        }
+    }
+
+    /// rush.json allows `//` and `/* */` comments, which serde_json rejects.
+    fn strip_comments(contents: &str) -> String {
+        let mut stripped = String::with_capacity(contents.len());
+        let mut chars = contents.chars().peekable();
+        let mut in_string = false;
+
+        while let Some(c) = chars.next() {
+            if in_string {
+                stripped.push(c);
+
+                if c == '\\' {
+                    if let Some(escaped) = chars.next() {
+                        stripped.push(escaped);
+                    }
+                } else if c == '"' {
+                    in_string = false;
+                }
+            } else if c == '/' && chars.peek() == Some(&'/') {
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            } else if c == '/' && chars.peek() == Some(&'*') {
+                chars.next();
+                let mut previous = ' ';
+
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            } else {
+                in_string = c == '"';
+                stripped.push(c);
+            }
+        }
+
+        stripped
+    }
+}
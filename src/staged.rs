@@ -0,0 +1,68 @@
+use git2::{Delta, Patch, Repository};
+
+/// A staged file from a diff of `HEAD` against the index.
+pub struct StagedChange {
+    pub path: String,
+    /// Whether the file is added by the commit.
+    pub is_new: bool,
+    /// Lines added and deleted, binary files count as a single line.
+    pub lines_changed: usize,
+    pub added_lines: Vec<String>,
+}
+
+pub fn get_staged_diff(repo: &Repository) -> Vec<StagedChange> {
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+
+    let diff = match repo.diff_tree_to_index(head_tree.as_ref(), None, None) {
+        Ok(diff) => diff,
+        Err(e) => {
+            log::info!("failed to diff the index {}\n", e);
+            return vec![];
+        }
+    };
+
+    let mut staged_changes = vec![];
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = match delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .and_then(|path| path.to_str())
+        {
+            Some(path) => path.to_owned(),
+            None => continue,
+        };
+
+        let mut lines_changed = 0;
+        let mut added_lines = vec![];
+
+        if let Ok(Some(patch)) = Patch::from_diff(&diff, idx) {
+            if let Ok((_, additions, deletions)) = patch.line_stats() {
+                lines_changed = additions + deletions;
+            }
+
+            for hunk_idx in 0..patch.num_hunks() {
+                let num_lines = patch.num_lines_in_hunk(hunk_idx).unwrap_or(0);
+
+                for line_idx in 0..num_lines {
+                    if let Ok(line) = patch.line_in_hunk(hunk_idx, line_idx) {
+                        if line.origin() == '+' {
+                            let content = String::from_utf8_lossy(line.content());
+                            added_lines.push(content.trim_end_matches('\n').to_owned());
+                        }
+                    }
+                }
+            }
+        }
+
+        staged_changes.push(StagedChange {
+            path,
+            is_new: delta.status() == Delta::Added,
+            lines_changed: lines_changed.max(1),
+            added_lines,
+        });
+    }
+
+    staged_changes
+}
@@ -0,0 +1,123 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Orders the scopes by the lines changed in their files, largest first,
+/// dropping those with less than `min_share` of the total. The largest
+/// scope is always kept.
+pub fn order_by_weight(
+    scoped_changes: BTreeMap<String, Vec<String>>,
+    line_counts: &HashMap<String, usize>,
+    min_share: Option<f64>,
+) -> Vec<String> {
+    let mut weights: Vec<(String, usize)> = scoped_changes
+        .into_iter()
+        .map(|(scope, paths)| {
+            let lines = paths
+                .iter()
+                .map(|path| line_counts.get(path).copied().unwrap_or(0))
+                .sum();
+
+            (scope, lines)
+        })
+        .collect();
+
+    // stable so scopes with the same weight stay sorted by name
+    weights.sort_by_key(|(_, lines)| std::cmp::Reverse(*lines));
+
+    log::info!("weighted scopes {:?}\n", weights);
+
+    let total: usize = weights.iter().map(|(_, lines)| lines).sum();
+    let min_lines = match min_share {
+        Some(min_share) => min_share * total as f64,
+        None => 0.0,
+    };
+
+    weights
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, (_, lines))| *idx == 0 || *lines as f64 >= min_lines)
+        .map(|(_, (scope, _))| scope)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use test_utilities::test_each;
+
+    #[test]
+    fn tests() {
+        struct TestCase<'a> {
+            description: &'a str,
+            scoped_changes: Vec<(&'a str, Vec<&'a str>)>,
+            min_share: Option<f64>,
+            expected_scopes: Vec<&'a str>,
+        }
+
+        let line_counts: HashMap<String, usize> = vec![
+            ("app/index.ts", 300),
+            ("app/util.ts", 100),
+            ("lib/index.ts", 1),
+            ("docs/README.md", 20),
+        ]
+        .into_iter()
+        .map(|(path, lines)| (path.to_owned(), lines))
+        .collect();
+
+        let test_cases = vec![
+            TestCase {
+                description: "orders scopes by lines changed",
+                scoped_changes: vec![
+                    ("app", vec!["app/index.ts", "app/util.ts"]),
+                    ("docs", vec!["docs/README.md"]),
+                    ("lib", vec!["lib/index.ts"]),
+                ],
+                min_share: None,
+                expected_scopes: vec!["app", "docs", "lib"],
+            },
+            TestCase {
+                description: "keeps scopes with the same weight sorted by name",
+                scoped_changes: vec![("b", vec!["docs/README.md"]), ("a", vec!["docs/README.md"])],
+                min_share: None,
+                expected_scopes: vec!["a", "b"],
+            },
+            TestCase {
+                description: "drops scopes below the min share",
+                scoped_changes: vec![
+                    ("app", vec!["app/index.ts"]),
+                    ("docs", vec!["docs/README.md"]),
+                    ("lib", vec!["lib/index.ts"]),
+                ],
+                min_share: Some(0.05),
+                expected_scopes: vec!["app", "docs"],
+            },
+            TestCase {
+                description: "keeps the largest scope whatever the min share",
+                scoped_changes: vec![
+                    ("docs", vec!["docs/README.md"]),
+                    ("lib", vec!["lib/index.ts"]),
+                ],
+                min_share: Some(1.0),
+                expected_scopes: vec!["docs"],
+            },
+        ];
+
+        test_each(test_cases, |tc| {
+            println!("Running test: {}", tc.description);
+
+            let scoped_changes = tc
+                .scoped_changes
+                .into_iter()
+                .map(|(scope, paths)| {
+                    let paths = paths.iter().map(|path| path.to_string()).collect();
+                    (scope.to_owned(), paths)
+                })
+                .collect();
+
+            assert_eq!(
+                order_by_weight(scoped_changes, &line_counts, tc.min_share),
+                tc.expected_scopes
+            );
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use serde::Deserialize;
+use std::{fs::File, path::Path, path::PathBuf};
+
+use crate::config::Config;
+use crate::manifest::build_packages_globs;
+use crate::package_json::PackageLocator;
+use crate::Monorepo;
+
+/// The `workspaces` field accepts either a list of globs or, as used by
+/// yarn for `nohoist`, an object with a `packages` list.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Workspaces {
+    Packages(Vec<String>),
+    Config { packages: Vec<String> },
+}
+
+impl Workspaces {
+    fn packages(&self) -> &Vec<String> {
+        match self {
+            Workspaces::Packages(packages) => packages,
+            Workspaces::Config { packages } => packages,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RootPackageJSON {
+    workspaces: Option<Workspaces>,
+}
+
+pub struct WorkspacesMonorepo {
+    packages: PackageLocator,
+}
+
+impl Monorepo for WorkspacesMonorepo {
+    fn new(repo_root: PathBuf, _config: &Config) -> Option<Box<dyn Monorepo>> {
+        let workspaces = WorkspacesMonorepo::parse_workspaces(&repo_root)?;
+        let globs = build_packages_globs(workspaces.packages())
+            .expect("invalid glob found in package.json workspaces");
+
+        Some(Box::new(WorkspacesMonorepo {
+            packages: PackageLocator::new(repo_root, globs),
+        }))
+    }
+
+    fn get_scope_for_file(&self, path: &str) -> Option<String> {
+        self.packages.get_package_name_for_file(path)
+    }
+
+    fn get_group_for_file(&self, path: &str) -> Option<String> {
+        self.packages.get_package_group_for_file(path)
+    }
+}
+
+impl WorkspacesMonorepo {
+    fn parse_workspaces(repo_path: &Path) -> Option<Workspaces> {
+        let package_json_file = File::open(repo_path.join("package.json")).ok()?;
+        let package_json: RootPackageJSON = serde_json::from_reader(package_json_file).ok()?;
+
+        package_json.workspaces
+    }
+}
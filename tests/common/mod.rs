@@ -43,7 +43,7 @@ impl<'a> TestOptionsBuilder<'a> {
     }
 }
 
-fn create_repo_from_fixture<'a>(fixture: &Path, dst: &Path, staged_paths: &Vec<&str>) {
+fn create_repo_from_fixture(fixture: &Path, dst: &Path, staged_paths: &Vec<&str>) {
     let repo = init_git_repo(dst);
     copy_fixture_to_tmp_repo(fixture, dst);
     add_working_dir_to_index(&repo, staged_paths);
@@ -66,7 +66,7 @@ fn init_git_repo(temp_dir: &Path) -> Repository {
     Repository::init_opts(temp_dir, &opts).expect("failed to initialise git repo")
 }
 
-fn copy_fixture_to_tmp_repo<'a>(fixture: &Path, repo: &Path) {
+fn copy_fixture_to_tmp_repo(fixture: &Path, repo: &Path) {
     let cargo_manifest = env::var_os("CARGO_MANIFEST_DIR").unwrap();
     let cargo_manifest_path = Path::new(cargo_manifest.to_str().unwrap());
     let abs_fixture_path = cargo_manifest_path.join(fixture);
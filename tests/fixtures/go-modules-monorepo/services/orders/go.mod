@@ -0,0 +1,3 @@
+module github.com/org/orders
+
+go 1.21
@@ -0,0 +1,3 @@
+module github.com/org/repo/libs/common
+
+go 1.21
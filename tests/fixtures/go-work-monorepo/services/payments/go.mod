@@ -0,0 +1,3 @@
+module github.com/org/repo/services/payments/v2
+
+go 1.21
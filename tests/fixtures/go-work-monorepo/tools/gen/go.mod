@@ -0,0 +1,3 @@
+module github.com/org/repo/tools/gen
+
+go 1.21
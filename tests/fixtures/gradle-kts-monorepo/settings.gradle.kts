@@ -0,0 +1,6 @@
+rootProject.name = "acme"
+
+include(
+    ":app",
+    ":libs:core",
+)
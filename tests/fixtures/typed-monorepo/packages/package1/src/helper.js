@@ -0,0 +1 @@
+const helper = () => 1;
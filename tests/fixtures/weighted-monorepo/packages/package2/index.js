@@ -0,0 +1,20 @@
+exports.value1 = 1;
+exports.value2 = 2;
+exports.value3 = 3;
+exports.value4 = 4;
+exports.value5 = 5;
+exports.value6 = 6;
+exports.value7 = 7;
+exports.value8 = 8;
+exports.value9 = 9;
+exports.value10 = 10;
+exports.value11 = 11;
+exports.value12 = 12;
+exports.value13 = 13;
+exports.value14 = 14;
+exports.value15 = 15;
+exports.value16 = 16;
+exports.value17 = 17;
+exports.value18 = 18;
+exports.value19 = 19;
+exports.value20 = 20;
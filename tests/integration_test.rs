@@ -1,4 +1,7 @@
-use nvim_conventional_commits::{CommitScopeFinder, DefaultScopeFinder};
+use nvim_conventional_commits::{
+    prepare_commit_msg::PrepareCommitMessage, CommitScopeFinder, DefaultScopeFinder,
+};
+use std::fs;
 mod common;
 use common::TestOptionsBuilder;
 
@@ -56,6 +59,200 @@ fn basic_tests() {
             staged_paths: vec!["apps/scoped"],
             expected_scopes: vec!["app4"],
         },
+        TestCase {
+            description: "it uses package.json workspaces when there is no lerna.json",
+            fixture: "tests/fixtures/workspaces-monorepo",
+            staged_paths: vec!["packages/package1/*", "packages/package2/*"],
+            expected_scopes: vec!["package1", "package2"],
+        },
+        TestCase {
+            description: "it ignores path not in package.json workspaces",
+            fixture: "tests/fixtures/workspaces-monorepo",
+            staged_paths: vec!["tools/tool1/*", "package.json"],
+            expected_scopes: vec![],
+        },
+        TestCase {
+            description: "it supports the object form of package.json workspaces",
+            fixture: "tests/fixtures/yarn-nohoist-monorepo",
+            staged_paths: vec!["packages/package2/*"],
+            expected_scopes: vec!["package2"],
+        },
+        TestCase {
+            description: "it uses pnpm-workspace.yaml packages",
+            fixture: "tests/fixtures/pnpm-monorepo",
+            staged_paths: vec!["packages/package1/*", "packages/package2/*"],
+            expected_scopes: vec!["package1", "package2"],
+        },
+        TestCase {
+            description: "it ignores paths excluded by negated pnpm-workspace.yaml globs",
+            fixture: "tests/fixtures/pnpm-monorepo",
+            staged_paths: vec!["packages/package1-test/*", "tools/tool1/*"],
+            expected_scopes: vec![],
+        },
+        TestCase {
+            description: "it uses Cargo.toml package names of workspace members",
+            fixture: "tests/fixtures/cargo-workspace",
+            staged_paths: vec!["crates/core/src/*", "tools/cli/*"],
+            expected_scopes: vec!["cli", "my-core"],
+        },
+        TestCase {
+            description: "it ignores crates excluded from the cargo workspace",
+            fixture: "tests/fixtures/cargo-workspace",
+            staged_paths: vec!["crates/experimental/*", "Cargo.toml"],
+            expected_scopes: vec![],
+        },
+        TestCase {
+            description: "it uses nx project.json names",
+            fixture: "tests/fixtures/nx-monorepo",
+            staged_paths: vec!["apps/app1-e2e/*", "libs/shared/ui/src/lib/*"],
+            expected_scopes: vec!["app1-e2e", "shared-ui"],
+        },
+        TestCase {
+            description: "it uses the directory name for unnamed nx projects",
+            fixture: "tests/fixtures/nx-monorepo",
+            staged_paths: vec!["libs/unnamed/*", "nx.json"],
+            expected_scopes: vec!["unnamed"],
+        },
+        TestCase {
+            description: "it uses nx workspace.json projects",
+            fixture: "tests/fixtures/nx-workspace-json-monorepo",
+            staged_paths: vec!["apps/app1/*", "libs/lib1/*"],
+            expected_scopes: vec!["app1", "lib1"],
+        },
+        TestCase {
+            description: "it uses the last segment of go.work modules",
+            fixture: "tests/fixtures/go-work-monorepo",
+            staged_paths: vec!["services/payments/api/*", "tools/gen/*", "go.work"],
+            expected_scopes: vec!["gen", "payments"],
+        },
+        TestCase {
+            description: "it discovers go.mod files when there is no go.work",
+            fixture: "tests/fixtures/go-modules-monorepo",
+            staged_paths: vec!["services/orders/vendor/*"],
+            expected_scopes: vec!["orders"],
+        },
+        TestCase {
+            description: "it uses the nearest bazel package for the scope",
+            fixture: "tests/fixtures/bazel-monorepo",
+            staged_paths: vec!["services/payments/*", "libs/common/*", "docs/*"],
+            expected_scopes: vec!["api", "common", "payments"],
+        },
+        TestCase {
+            description: "it does not cross into nested bazel workspaces",
+            fixture: "tests/fixtures/bazel-monorepo",
+            staged_paths: vec!["third_party/nested/*", "MODULE.bazel"],
+            expected_scopes: vec![],
+        },
+        TestCase {
+            description: "it collapses bazel packages to the configured depth",
+            fixture: "tests/fixtures/bazel-depth-monorepo",
+            staged_paths: vec!["services/payments/api/*", "libs/common/*"],
+            expected_scopes: vec!["common", "payments"],
+        },
+        TestCase {
+            description: "it uses normalized pyproject.toml project names",
+            fixture: "tests/fixtures/python-monorepo",
+            staged_paths: vec!["libs/*", "services/ingest/*"],
+            expected_scopes: vec!["data-utils", "ingest", "ml-core"],
+        },
+        TestCase {
+            description: "it ignores pyproject.toml outside the configured packages",
+            fixture: "tests/fixtures/python-monorepo",
+            staged_paths: vec!["scratch/notebook/*", "pyproject.toml"],
+            expected_scopes: vec![],
+        },
+        TestCase {
+            description: "it uses the artifactId of nested maven modules",
+            fixture: "tests/fixtures/maven-monorepo",
+            staged_paths: vec!["core/*", "services/billing/*", "docs/*"],
+            expected_scopes: vec!["acme-core", "billing-service"],
+        },
+        TestCase {
+            description: "it uses gradle project names from settings.gradle includes",
+            fixture: "tests/fixtures/gradle-monorepo",
+            staged_paths: vec!["app/*", "libs/core/*", "build-logic/*"],
+            expected_scopes: vec!["app", "core"],
+        },
+        TestCase {
+            description: "it follows groovy includes continued on the next line",
+            fixture: "tests/fixtures/gradle-monorepo",
+            staged_paths: vec!["tools/gen/*"],
+            expected_scopes: vec!["gen"],
+        },
+        TestCase {
+            description: "it uses multi-line includes of settings.gradle.kts",
+            fixture: "tests/fixtures/gradle-kts-monorepo",
+            staged_paths: vec!["app/*", "libs/core/*", "tools/gen/*"],
+            expected_scopes: vec!["app", "core"],
+        },
+        TestCase {
+            description: "it uses rush.json projects",
+            fixture: "tests/fixtures/rush-monorepo",
+            staged_paths: vec![
+                "apps/web/src/*",
+                "libs/utils/*",
+                "tools/cli/*",
+                "tools/unlisted/*",
+            ],
+            expected_scopes: vec!["cli", "utils", "web"],
+        },
+        TestCase {
+            description: "it applies the configured scope aliases",
+            fixture: "tests/fixtures/aliased-monorepo",
+            staged_paths: vec!["apps/*", "libs/*"],
+            expected_scopes: vec!["dashboard", "ui"],
+        },
+        TestCase {
+            description: "it reports groups with several changed packages",
+            fixture: "tests/fixtures/grouped-monorepo",
+            staged_paths: vec!["apps/web/*", "libs/*"],
+            expected_scopes: vec!["apps/web", "libs"],
+        },
+        TestCase {
+            description: "it reports every group with several changed packages",
+            fixture: "tests/fixtures/grouped-monorepo",
+            staged_paths: vec!["apps/*", "libs/*", "lerna.json"],
+            expected_scopes: vec!["apps", "libs"],
+        },
+        TestCase {
+            description: "it uses the root scope when no package is changed",
+            fixture: "tests/fixtures/root-scope-monorepo",
+            staged_paths: vec![".github/*", "tsconfig.base.json"],
+            expected_scopes: vec!["repo"],
+        },
+        TestCase {
+            description: "it leaves out the root scope when a package is changed",
+            fixture: "tests/fixtures/root-scope-monorepo",
+            staged_paths: vec!["packages/package1/*", "tsconfig.base.json"],
+            expected_scopes: vec!["package1"],
+        },
+        TestCase {
+            description: "it adds the root scope to package scopes when configured",
+            fixture: "tests/fixtures/root-scope-always-monorepo",
+            staged_paths: vec!["packages/package1/*", "package.json"],
+            expected_scopes: vec!["package1", "repo"],
+        },
+        TestCase {
+            description: "it does not add the root scope when every file is in a package",
+            fixture: "tests/fixtures/root-scope-always-monorepo",
+            staged_paths: vec!["packages/package1/*"],
+            expected_scopes: vec!["package1"],
+        },
+        TestCase {
+            description: "it does not scope ignored files",
+            fixture: "tests/fixtures/ignored-paths-monorepo",
+            staged_paths: vec![
+                "packages/package1/CHANGELOG.md",
+                "packages/package2/package-lock.json",
+            ],
+            expected_scopes: vec![],
+        },
+        TestCase {
+            description: "it scopes files next to ignored files",
+            fixture: "tests/fixtures/ignored-paths-monorepo",
+            staged_paths: vec!["packages/package1/*", "packages/package2/package-lock.json"],
+            expected_scopes: vec!["package1"],
+        },
     ];
 
     test_each(test_cases, |tc| {
@@ -83,3 +280,250 @@ fn it_handles_repo_subdirectory() {
 
     assert_eq!(commit_msg, vec!["package1"]);
 }
+
+#[test]
+fn it_reports_the_detected_backend() {
+    let options = TestOptionsBuilder::new("tests/fixtures/lerna-monorepo").build();
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.backend_name(), Some("lerna"));
+
+    let options = TestOptionsBuilder::new("tests/fixtures/simple-repo").build();
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.backend_name(), None);
+}
+
+#[test]
+fn it_uses_the_backend_forced_in_config() {
+    let options = TestOptionsBuilder::new("tests/fixtures/forced-backend-monorepo")
+        .with_staged_paths(vec!["packages/package1/*", "tools/tool1/*"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.backend_name(), Some("workspaces"));
+    assert_eq!(cch.get_commit_scopes(), vec!["tool1"]);
+}
+
+#[test]
+fn it_combines_backends_rooted_at_subdirectories() {
+    let options = TestOptionsBuilder::new("tests/fixtures/polyglot-monorepo")
+        .with_staged_paths(vec!["crates/api/*", "web/apps/*", "services/billing/*"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.backend_name(), Some("composite"));
+    assert_eq!(cch.get_commit_scopes(), vec!["api", "billing", "dashboard"]);
+}
+
+#[test]
+fn it_uses_scope_mappings_from_config() {
+    let options = TestOptionsBuilder::new("tests/fixtures/mapped-scopes-repo")
+        .with_staged_paths(vec!["docs/*", "infra/*", "src/*"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.backend_name(), Some("mappings"));
+    assert_eq!(cch.get_commit_scopes(), vec!["docs", "infra"]);
+}
+
+#[test]
+fn it_combines_scope_mappings_from_package_json_with_the_detected_backend() {
+    let options = TestOptionsBuilder::new("tests/fixtures/package-json-scopes-monorepo")
+        .with_staged_paths(vec!["docs/*", "packages/package1/*"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.backend_name(), Some("mappings+lerna"));
+    assert_eq!(cch.get_commit_scopes(), vec!["docs", "package1"]);
+}
+
+#[test]
+fn it_uses_codeowners_for_the_scope() {
+    let test_cases = vec![
+        TestCase {
+            description: "it maps owners to scopes using the last matching rule",
+            fixture: "tests/fixtures/codeowners-repo",
+            staged_paths: vec!["docs/*", "infra/*", "services/*"],
+            expected_scopes: vec!["docs", "infra-team", "payments"],
+        },
+        TestCase {
+            description: "it derives the scope from owners without a mapping",
+            fixture: "tests/fixtures/codeowners-repo",
+            staged_paths: vec!["README.md", "web/*"],
+            expected_scopes: vec!["dev", "platform"],
+        },
+    ];
+
+    test_each(test_cases, |tc| {
+        println!("Running test: {}", tc.description);
+
+        let options = TestOptionsBuilder::new(tc.fixture)
+            .with_staged_paths(tc.staged_paths)
+            .build();
+
+        let cch = DefaultScopeFinder::new(&options.git_repo);
+
+        assert_eq!(cch.backend_name(), Some("codeowners"));
+        assert_eq!(cch.get_commit_scopes(), tc.expected_scopes);
+    })
+}
+
+#[test]
+fn it_orders_scopes_by_lines_changed() {
+    let options = TestOptionsBuilder::new("tests/fixtures/weighted-monorepo")
+        .with_staged_paths(vec!["packages/*"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.get_commit_scopes(), vec!["package2", "package1"]);
+}
+
+#[test]
+fn it_infers_the_commit_type_from_the_staged_changes() {
+    struct TypeTestCase<'a> {
+        description: &'a str,
+        staged_paths: Vec<&'a str>,
+        expected_type: Option<&'a str>,
+    }
+
+    let test_cases = vec![
+        TypeTestCase {
+            description: "it infers docs for markdown files",
+            staged_paths: vec!["packages/package1/README.md"],
+            expected_type: Some("docs"),
+        },
+        TypeTestCase {
+            description: "it infers test for test files",
+            staged_paths: vec!["packages/package1/src/index.test.js"],
+            expected_type: Some("test"),
+        },
+        TypeTestCase {
+            description: "it infers ci for workflows",
+            staged_paths: vec![".github/*"],
+            expected_type: Some("ci"),
+        },
+        TypeTestCase {
+            description: "it infers build for build config",
+            staged_paths: vec!["packages/package1/package.json", "lerna.json"],
+            expected_type: Some("build"),
+        },
+        TypeTestCase {
+            description: "it infers feat for new files with exports",
+            staged_paths: vec![
+                "packages/package1/src/index.js",
+                "packages/package1/README.md",
+            ],
+            expected_type: Some("feat"),
+        },
+        TypeTestCase {
+            description: "it infers nothing for other changes",
+            staged_paths: vec!["packages/package1/src/helper.js"],
+            expected_type: None,
+        },
+    ];
+
+    test_each(test_cases, |tc| {
+        println!("Running test: {}", tc.description);
+
+        let options = TestOptionsBuilder::new("tests/fixtures/typed-monorepo")
+            .with_staged_paths(tc.staged_paths)
+            .build();
+
+        let cch = DefaultScopeFinder::new(&options.git_repo);
+
+        assert_eq!(cch.get_commit_type().as_deref(), tc.expected_type);
+    })
+}
+
+#[test]
+fn it_writes_the_inferred_commit_type_of_files_outside_packages() {
+    let options = TestOptionsBuilder::new("tests/fixtures/typed-monorepo")
+        .with_staged_paths(vec![".github/workflows/ci.yml"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+    let commit_msg_file = options.git_repo.join(".git/COMMIT_EDITMSG");
+    fs::write(&commit_msg_file, "\n# Please enter the commit message\n").unwrap();
+
+    PrepareCommitMessage {
+        commit_msg_file: &commit_msg_file,
+        commit_source: None,
+        commit_msg_generator: &cch,
+        config: cch.config(),
+    }
+    .update_commit()
+    .unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&commit_msg_file).unwrap(),
+        "ci:\n\n# Please enter the commit message\n"
+    );
+}
+
+#[test]
+fn it_infers_no_commit_type_without_a_backend_or_root_scope() {
+    let options = TestOptionsBuilder::new("tests/fixtures/simple-repo")
+        .with_staged_paths(vec!["README.md"])
+        .build();
+
+    let cch = DefaultScopeFinder::new(&options.git_repo);
+
+    assert_eq!(cch.get_commit_type(), None);
+}
+
+#[test]
+fn it_applies_the_commitlint_rules() {
+    struct CommitlintTestCase<'a> {
+        description: &'a str,
+        commit_source: Option<&'a str>,
+        commit_msg_contents: &'a str,
+        expected_commit: &'a str,
+    }
+
+    let test_cases = vec![
+        CommitlintTestCase {
+            description: "it applies the scope-case, scope-enum and type-enum",
+            commit_source: None,
+            commit_msg_contents: "",
+            expected_commit: "deps(package-one):\n",
+        },
+        CommitlintTestCase {
+            description: "it accepts the types of the type-enum",
+            commit_source: Some("message"),
+            commit_msg_contents: "deps: bump react",
+            expected_commit: "deps(package-one): bump react",
+        },
+    ];
+
+    test_each(test_cases, |tc| {
+        println!("Running test: {}", tc.description);
+
+        let options = TestOptionsBuilder::new("tests/fixtures/commitlint-monorepo")
+            .with_staged_paths(vec!["packages/*"])
+            .build();
+
+        let cch = DefaultScopeFinder::new(&options.git_repo);
+        let commit_msg_file = options.git_repo.join(".git/COMMIT_EDITMSG");
+        fs::write(&commit_msg_file, tc.commit_msg_contents).unwrap();
+
+        PrepareCommitMessage {
+            commit_msg_file: &commit_msg_file,
+            commit_source: tc.commit_source,
+            commit_msg_generator: &cch,
+            config: cch.config(),
+        }
+        .update_commit()
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&commit_msg_file).unwrap(),
+            tc.expected_commit
+        );
+    })
+}